@@ -1,71 +1,130 @@
 use std::vec;
 
 use crate::error::ContractError;
+use crate::migrations::{assert_migrated, run_migrations, MigrationStep};
 use crate::state::{
-    bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, ADMINS, CONFIG, IS_HALTED,
-    LIVE_ROUND, NEXT_ROUND, NEXT_ROUND_ID, ROUNDS, TOTALS_SPENT,
+    bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, leaderboard_net_pnl,
+    leaderboard_storage, may_load_finished_round, range_finished_rounds, AmmObservation,
+    ArchivedRound, AMM_OBSERVATIONS, ARCHIVED_ROUNDS, CONFIG, DAILY_STATS, EMERGENCY,
+    FEEDER_PRICES, GLOBAL_STATS, GOVERNANCE, LIVE_ROUND, NEXT_CONFIG_CHANGE_ID, NEXT_ROUND,
+    NEXT_ROUND_ID, NEXT_SEASON_ID, OWNERSHIP_PROPOSALS, PAUSE_STATE, PENDING_CONFIG_CHANGES,
+    PLAYER_STATS, PRICE_HISTORY, PRUNED_CLAIMS, ROLES, ROUNDS, SEASONS, TOTALS_SPENT,
+    UNSCORED_ROUNDS,
 };
-use cw0::one_coin;
+use cw0::{nonpayable, one_coin};
 use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use fuzio_bet::fuzio_prediction_game::{
-    AdminsResponse, BetInfo, ClaimInfo, ClaimInfoResponse, ConfigResponse, MyGameResponse,
-    PendingRewardResponse, PendingRewardRoundsResponse, RoundUsersResponse, TotalSpentResponse,
-    WalletInfo,
+    AdminsResponse, BetInfo, ClaimExpiryResponse, ClaimInfo, ClaimInfoResponse, ConfigResponse,
+    DailyStats, EmergencyResponse, EmergencyState, FeederPrice, FeederPricesResponse, GlobalStats,
+    GlobalStatsResponse, GovernanceResponse, LeaderboardEntry, LeaderboardMetric,
+    LeaderboardPeriod, LeaderboardResponse, LeaderboardScore, MyGameResponse, OddsResponse,
+    OrderBy, PendingRewardResponse, PendingRewardRoundsResponse, PlayerStats, PlayerStatsResponse,
+    PriceHistoryResponse, PriceSample, RoundSummary, RoundUsersResponse, RoundsResponse, Season,
+    SeasonPrize, SeasonsResponse, SimulateBetResponse, TotalSpentResponse, WalletInfo,
+};
+use fuzio_bet::fuzio_prediction_game::{
+    Config, ConfigUpdate, Direction, OptionalConfigField, OwnershipChange, OwnershipProposal,
+    OwnershipProposalResponse, PauseState, PendingConfigChange, PendingConfigChangesResponse,
+    PoolType, PriceSource, PricingMethod, Role, RolesResponse, SourcePrice,
 };
-use fuzio_bet::fuzio_prediction_game::{Config, Direction};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Env, Event, MessageInfo,
-    Order, Response, StdError, StdResult, Uint128,
+    coin, coins, to_binary, Addr, Api, BankMsg, Binary, Coin, Decimal, Deps, DepsMut, Env, Event,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128,
+    Uint64, WasmMsg,
 };
 use cw_storage_plus::Bound;
-use fuzio_bet::fuzio_prediction_game::{FinishedRound, LiveRound, NextRound, FEE_PRECISION};
+use fuzio_bet::events::{
+    config_update_attributes, BetPlaced, Claimed, ConfigChanged, FeesDistributed, RoundLocked,
+    RoundSettled, TypedEvent,
+};
+use fuzio_bet::fuzio_prediction_game::{
+    FinishedRound, LiveRound, NextRound, FEE_PRECISION, MAX_GAMING_FEE,
+};
 use fuzio_bet::fuzio_prediction_game::{MyCurrentPositionResponse, StatusResponse};
-use sei_cosmwasm::{ExchangeRatesResponse, SeiQuerier, SeiQueryWrapper};
+use fuzio_bet::{amm, pyth};
+use sei_cosmwasm::{ExchangeRatesResponse, OracleTwapsResponse, SeiQuerier, SeiQueryWrapper};
 
 // Query limits
 const DEFAULT_QUERY_LIMIT: u32 = 10;
 const MAX_QUERY_LIMIT: u32 = 30;
 
+// Entries a paginated migration goes through per call
+const DEFAULT_MIGRATION_LIMIT: u32 = 500;
+
+// Rounds, bets and claims a prune goes through per call, pruned or not
+const MAX_PRUNE_SCAN: usize = 300;
+
+// Bets scored when rounds close, and per ScoreRounds call by default and at most
+const CLOSE_SCORE_LIMIT: usize = 30;
+const DEFAULT_SCORE_LIMIT: u32 = 100;
+const MAX_SCORE_LIMIT: u32 = 500;
+
+// Bets swept per call by default and at most
+const DEFAULT_SWEEP_LIMIT: u32 = 100;
+const MAX_SWEEP_LIMIT: u32 = 500;
+
+const DAY: u64 = 24 * 60 * 60;
+
+// Daily stats buckets returned by default and at most
+const DEFAULT_STATS_DAYS: u32 = 7;
+const MAX_STATS_DAYS: u32 = 90;
+
+// Samples the price TWAP can average at most
+const MAX_TWAP_SAMPLES: u32 = 100;
+
+// Sources a median price can have
+const MAX_PRICE_SOURCES: usize = 10;
+
+// Stable pools are priced by simulating a swap of this fraction of the reserve
+const STABLE_PRICE_PROBE: u128 = 10_000;
+
+// Ranks a season can pay out, and players read to find them
+const MAX_SEASON_WINNERS: usize = 30;
+const MAX_SEASON_RANKING_SCAN: usize = 300;
+
+// Ownership proposals are valid for a week unless told otherwise
+const DEFAULT_PROPOSAL_EXPIRY: u64 = 7 * 24 * 60 * 60;
+
+// What to carry on with once Pyth price updates are pushed
+const REPLY_CLOSE_ROUND: u64 = 1;
+const REPLY_CHECKPOINT_PRICE: u64 = 2;
+
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    let mut total_ratio = Decimal::zero();
-    for dev_wallet in msg.config.dev_wallet_list.clone() {
-        total_ratio = total_ratio + dev_wallet.ratio;
-    }
-
-    if total_ratio != Decimal::one() {
-        return Err(ContractError::WrongRatio {});
-    }
+    validate_config(deps.as_ref(), &env, &msg.config)?;
 
     CONFIG.save(deps.storage, &msg.config)?;
     NEXT_ROUND_ID.save(deps.storage, &0u128)?;
-    IS_HALTED.save(deps.storage, &false)?;
-    ADMINS.save(deps.storage, &vec![info.sender])?;
+    PAUSE_STATE.save(deps.storage, &PauseState::default())?;
+    ROLES.save(deps.storage, info.sender, &vec![Role::Owner])?;
 
     Ok(Response::new())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, MigrateMsg {}: MigrateMsg) -> StdResult<Response> {
-    let version = cw2::get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
-    }
-    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from = cw2::get_contract_version(deps.storage)?.version;
+    let limit = msg.limit.unwrap_or(DEFAULT_MIGRATION_LIMIT);
+    let (reached, step) = run_migrations(deps.storage, CONTRACT_NAME, CONTRACT_VERSION, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-migrate")
+        .add_attribute("from_version", from)
+        .add_attribute("to_version", reached)
+        .add_attribute("done", (step == MigrationStep::Done).to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -75,25 +134,130 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    assert_migrated(deps.storage, CONTRACT_VERSION)?;
+
     match msg {
-        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, info, config),
+        ExecuteMsg::UpdateConfig { config } => execute_update_config(deps, info, env, *config),
+        ExecuteMsg::ExecuteConfigChange { id } => execute_config_change(deps, env, id),
+        ExecuteMsg::CancelConfigChange { id } => execute_cancel_config_change(deps, info, id),
         ExecuteMsg::BetBear { round_id, amount } => {
             execute_bet(deps, info, env, round_id, Direction::Bear, amount)
         }
         ExecuteMsg::BetBull { round_id, amount } => {
             execute_bet(deps, info, env, round_id, Direction::Bull, amount)
         }
-        ExecuteMsg::CloseRound {} => execute_close_round(deps, env),
-        ExecuteMsg::CollectWinnings {} => execute_collect_winnings(deps, info),
-        ExecuteMsg::CollectionWinningRound { round_id } => {
-            execute_collect_winning_round(deps, info, round_id)
+        ExecuteMsg::CloseRound { price_updates } => match price_updates {
+            Some(data) => execute_update_pyth_prices(deps.as_ref(), info, data, REPLY_CLOSE_ROUND),
+            None => execute_close_round(deps, env),
+        },
+        ExecuteMsg::CheckpointPrice { price_updates } => {
+            execute_checkpoint_price(deps, info, env, price_updates)
+        }
+        ExecuteMsg::PushPrice {
+            market,
+            price,
+            timestamp,
+        } => execute_push_price(deps, info, env, market, price, timestamp),
+        ExecuteMsg::CollectWinnings { recipient } => {
+            let player = info.sender.clone();
+            execute_collect_winnings(deps, info, env, player, recipient)
+        }
+        ExecuteMsg::CollectionWinningRound {
+            round_id,
+            recipient,
+        } => execute_collect_winning_round(deps, info, env, round_id, recipient),
+        ExecuteMsg::CollectWinningsFor { player } => {
+            /* Paid to the player, the sender only relays the claim */
+            execute_collect_winnings(deps, info, env, player, None)
+        }
+        ExecuteMsg::ClaimAndBet { direction, amount } => {
+            execute_claim_and_bet(deps, info, env, direction, amount)
+        }
+        ExecuteMsg::SweepExpired { round_ids, limit } => {
+            execute_sweep_expired(deps, info, env, round_ids, limit)
+        }
+        ExecuteMsg::PruneHistory {
+            before_round,
+            start_after,
+            limit,
+        } => execute_prune_history(deps, info, before_round, start_after, limit),
+        ExecuteMsg::ScoreRounds { limit } => execute_score_rounds(deps, limit),
+        ExecuteMsg::EmergencyShutdown {} => execute_emergency_shutdown(deps, info, env),
+        ExecuteMsg::EmergencyWithdraw {} => execute_emergency_withdraw(deps, info, env),
+        /* Like before claims could be paused, the legacy halt leaves them open */
+        ExecuteMsg::Halt {} => execute_set_pause(deps, info, Some(true), Some(true), None),
+        ExecuteMsg::Resume {} => execute_set_pause(deps, info, Some(false), Some(false), None),
+        ExecuteMsg::SetPause {
+            betting,
+            rounds,
+            claims,
+        } => execute_set_pause(deps, info, betting, rounds, claims),
+        ExecuteMsg::AddAdmin {
+            new_admin,
+            expires_in,
+        } => execute_propose_ownership(
+            deps,
+            info,
+            env,
+            new_admin,
+            OwnershipChange::AddAdmin,
+            expires_in,
+        ),
+        ExecuteMsg::TransferOwnership {
+            new_owner,
+            expires_in,
+        } => execute_propose_ownership(
+            deps,
+            info,
+            env,
+            new_owner,
+            OwnershipChange::Transfer,
+            expires_in,
+        ),
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, info, env),
+        ExecuteMsg::HandOverToGovernance {
+            governance,
+            expires_in,
+        } => execute_propose_ownership(
+            deps,
+            info,
+            env,
+            governance,
+            OwnershipChange::Governance,
+            expires_in,
+        ),
+        ExecuteMsg::CancelOwnershipProposal { candidate } => {
+            execute_cancel_ownership_proposal(deps, info, candidate)
         }
-        ExecuteMsg::Halt {} => execute_update_halt(deps, info, true),
-        ExecuteMsg::Resume {} => execute_update_halt(deps, info, false),
-        ExecuteMsg::AddAdmin { new_admin } => execute_add_admin(deps, info, new_admin),
         ExecuteMsg::RemoveAdmin { old_admin } => execute_remove_admin(deps, info, old_admin),
+        ExecuteMsg::GrantRole { address, role } => {
+            execute_grant_role(deps, info, env, address, role)
+        }
+        ExecuteMsg::RevokeRole { address, role } => execute_revoke_role(deps, info, address, role),
+        ExecuteMsg::CreateSeason {
+            start_time,
+            end_time,
+            min_bet,
+            min_rounds,
+            fee_share,
+            prize_curve,
+        } => execute_create_season(
+            deps,
+            info,
+            env,
+            start_time,
+            end_time,
+            min_bet,
+            min_rounds,
+            fee_share,
+            prize_curve,
+        ),
+        ExecuteMsg::FundSeason { season_id } => execute_fund_season(deps, info, season_id),
+        ExecuteMsg::FinalizeSeason { season_id } => {
+            execute_finalize_season(deps, info, env, season_id)
+        }
         ExecuteMsg::ModifyDevWallet { new_dev_wallets } => {
-            execute_modify_dev_wallets(deps, info, new_dev_wallets)
+            execute_modify_dev_wallets(deps, info, env, new_dev_wallets)
         }
     }
 }
@@ -101,88 +265,25 @@ pub fn execute(
 fn execute_collect_winnings(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
+    env: Env,
+    player: Addr,
+    recipient: Option<Addr>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref(), Pausable::Claims)?;
     let config = CONFIG.load(deps.storage)?;
+    let player = deps.api.addr_validate(player.as_str())?;
+    let recipient = validate_recipient(deps.as_ref(), recipient, &player)?;
     let mut winnings = Uint128::zero();
+    let mut amount_commissionable = Uint128::zero();
     let mut resp = Response::new();
 
-    let my_game_list = query_my_games_without_limit(deps.as_ref(), info.sender.clone())?;
-    let live_round = LIVE_ROUND.load(deps.storage)?;
-    let mut amount_commissionable = Uint128::zero();
+    let my_game_list = query_my_games_without_limit(deps.as_ref(), player.clone())?;
 
     for game in my_game_list.my_game_list {
-        let round_id = game.round_id;
-
-        if live_round.id == round_id {
-            continue;
-        }
-
-        let round = ROUNDS.load(deps.storage, round_id.u128())?;
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-        let bet_info_key = bet_info_key(round_id.u128(), &info.sender);
-
-        bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
-
-        let claim_info_key = claim_info_key(round_id.u128(), &info.sender);
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            winnings += game.amount;
-            if game.amount > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
-            }
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
-
-            /* Count it up */
-            winnings += round_winnings;
-            amount_commissionable += round_winnings;
-
-            if round_winnings > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
-            }
-        }
+        let (round_winnings, round_commissionable) =
+            settle_game(deps.storage, &config, &env, &game, &info.sender, &recipient)?;
+        winnings += round_winnings;
+        amount_commissionable += round_commissionable;
     }
 
     if winnings == Uint128::zero() {
@@ -193,120 +294,59 @@ fn execute_collect_winnings(
 
     let mut dev_fee = Uint128::zero();
     if amount_commissionable != Uint128::zero() {
-        dev_fee = compute_gaming_fee(deps.as_ref(), amount_commissionable)?;
-        let mut messages_dev_fees = Vec::new();
-        for dev_wallet in config.clone().dev_wallet_list {
-            let token_transfer_msg = BankMsg::Send {
-                to_address: dev_wallet.address.to_string(),
-                amount: coins((dev_fee * dev_wallet.ratio).u128(), &config.token_denom),
-            };
-            messages_dev_fees.push(token_transfer_msg)
-        }
+        let (messages_dev_fees, fees_distributed);
+        (dev_fee, messages_dev_fees, fees_distributed) =
+            distribute_gaming_fee(deps.storage, &env, &config, amount_commissionable)?;
         resp = resp
             .add_messages(messages_dev_fees)
-            .add_attribute("action", "fuzio-distribute-dev-rewards")
-            .add_attribute("amount", dev_fee);
+            .add_event(fees_distributed.to_event());
     }
 
-    let amount_winnings = winnings.u128() - dev_fee.u128();
+    let amount_winnings = winnings - dev_fee;
+    record_payout(
+        deps.storage,
+        &env,
+        amount_winnings,
+        dev_fee,
+        &config.token_denom,
+    )?;
     let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+        to_address: recipient.to_string(),
+        amount: coins(amount_winnings.u128(), &config.token_denom),
     };
 
     Ok(resp
         .add_message(msg_send_winnings)
         .add_attribute("action", "fuzio-collect-winnings")
-        .add_attribute("amount", amount_winnings.to_string()))
+        .add_event(
+            Claimed {
+                player,
+                claimed_by: info.sender,
+                recipient,
+                amount: amount_winnings,
+            }
+            .to_event(),
+        ))
 }
 
 fn execute_collect_winning_round(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
+    env: Env,
     round_id: Uint128,
+    recipient: Option<Addr>,
 ) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref(), Pausable::Claims)?;
     let config = CONFIG.load(deps.storage)?;
+    let recipient = validate_recipient(deps.as_ref(), recipient, &info.sender)?;
     let mut winnings = Uint128::zero();
+    let mut amount_commissionable = Uint128::zero();
     let mut resp = Response::new();
 
-    let mut my_game_list: Vec<BetInfo> = Vec::new();
-
     let bet_info_key_round = bet_info_key(round_id.u128(), &info.sender);
-    let game = bet_info_storage().may_load(deps.storage, bet_info_key_round)?;
-    match game {
-        Some(_game) => my_game_list.push(_game),
-        None => {}
-    }
-
-    let mut amount_commissionable = Uint128::zero();
-
-    for game in my_game_list {
-        let round_id = game.round_id;
-        let round = ROUNDS.load(deps.storage, round_id.u128())?;
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-        let bet_info_key = bet_info_key(round_id.u128(), &info.sender);
-
-        bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
-
-        let claim_info_key = claim_info_key(round_id.u128(), &info.sender);
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            winnings += game.amount;
-            if game.amount > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
-            }
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
-
-            /* Count it up */
-            winnings += round_winnings;
-            amount_commissionable += round_winnings;
-            if round_winnings > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
-            }
-        }
+    if let Some(game) = bet_info_storage().may_load(deps.storage, bet_info_key_round)? {
+        (winnings, amount_commissionable) =
+            settle_game(deps.storage, &config, &env, &game, &info.sender, &recipient)?;
     }
 
     if winnings == Uint128::zero() {
@@ -317,255 +357,1567 @@ fn execute_collect_winning_round(
 
     let mut dev_fee = Uint128::zero();
     if amount_commissionable != Uint128::zero() {
-        dev_fee = compute_gaming_fee(deps.as_ref(), amount_commissionable)?;
-        let mut messages_dev_fees = Vec::new();
-        for dev_wallet in config.clone().dev_wallet_list {
-            let token_transfer_msg = BankMsg::Send {
-                to_address: dev_wallet.address.to_string(),
-                amount: coins((dev_fee * dev_wallet.ratio).u128(), &config.token_denom),
-            };
-            messages_dev_fees.push(token_transfer_msg)
-        }
+        let (messages_dev_fees, fees_distributed);
+        (dev_fee, messages_dev_fees, fees_distributed) =
+            distribute_gaming_fee(deps.storage, &env, &config, amount_commissionable)?;
         resp = resp
             .add_messages(messages_dev_fees)
-            .add_attribute("action", "fuzio-distribute-dev-rewards")
-            .add_attribute("amount", dev_fee);
+            .add_event(fees_distributed.to_event());
     }
 
-    let amount_winnings = winnings.u128() - dev_fee.u128();
+    let amount_winnings = winnings - dev_fee;
+    record_payout(
+        deps.storage,
+        &env,
+        amount_winnings,
+        dev_fee,
+        &config.token_denom,
+    )?;
     let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+        to_address: recipient.to_string(),
+        amount: coins(amount_winnings.u128(), &config.token_denom),
     };
 
     Ok(resp
         .add_message(msg_send_winnings)
         .add_attribute("action", "fuzio-collect-winnings-round")
         .add_attribute("round_id", round_id)
-        .add_attribute("amount", amount_winnings.to_string()))
+        .add_event(
+            Claimed {
+                player: info.sender.clone(),
+                claimed_by: info.sender,
+                recipient,
+                amount: amount_winnings,
+            }
+            .to_event(),
+        ))
 }
 
-fn execute_bet(
+fn execute_claim_and_bet(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
     env: Env,
-    round_id: Uint128,
     dir: Direction,
-    gross: Uint128,
+    amount: Option<Uint128>,
 ) -> Result<Response, ContractError> {
-    assert_not_halted(deps.as_ref())?;
+    assert_not_paused(deps.as_ref(), Pausable::Claims)?;
+    assert_not_paused(deps.as_ref(), Pausable::Betting)?;
+    assert_not_emergency(deps.as_ref())?;
+    nonpayable(&info)?;
 
-    let mut bet_round = assert_is_current_round(deps.as_ref(), round_id)?;
-    let mut resp = Response::new();
     let config = CONFIG.load(deps.storage)?;
+    let bet_round = NEXT_ROUND.load(deps.storage)?;
+    let mut winnings = Uint128::zero();
+    let mut amount_commissionable = Uint128::zero();
+    let mut resp = Response::new();
 
-    let funds_sent = one_coin(&info)?;
+    let my_game_list = query_my_games_without_limit(deps.as_ref(), info.sender.clone())?;
 
-    let totals = TOTALS_SPENT.may_load(deps.storage, info.clone().sender)?;
+    for game in my_game_list.my_game_list {
+        let (round_winnings, round_commissionable) = settle_game(
+            deps.storage,
+            &config,
+            &env,
+            &game,
+            &info.sender,
+            &info.sender,
+        )?;
+        winnings += round_winnings;
+        amount_commissionable += round_commissionable;
+    }
 
-    if totals.is_none() {
-        TOTALS_SPENT.save(deps.storage, info.clone().sender, &funds_sent.amount)?;
-    } else {
-        let new_totals = totals.unwrap() + funds_sent.amount;
-        TOTALS_SPENT.save(deps.storage, info.clone().sender, &new_totals)?;
+    if winnings == Uint128::zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Nothing to claim",
+        )));
     }
 
-    if funds_sent.denom != config.token_denom {
-        return Err(ContractError::InvalidFunds {});
+    let mut dev_fee = Uint128::zero();
+    if amount_commissionable != Uint128::zero() {
+        let (messages_dev_fees, fees_distributed);
+        (dev_fee, messages_dev_fees, fees_distributed) =
+            distribute_gaming_fee(deps.storage, &env, &config, amount_commissionable)?;
+        resp = resp
+            .add_messages(messages_dev_fees)
+            .add_event(fees_distributed.to_event());
     }
 
-    if funds_sent.amount != gross {
+    let amount_winnings = winnings - dev_fee;
+    record_payout(
+        deps.storage,
+        &env,
+        amount_winnings,
+        dev_fee,
+        &config.token_denom,
+    )?;
+    let gross = amount.unwrap_or(amount_winnings);
+    if gross.is_zero() || gross > amount_winnings {
         return Err(ContractError::NotEnoughFunds {});
     }
 
-    if env.block.time > bet_round.open_time {
-        return Err(ContractError::Std(StdError::generic_err(format!(
-            "Round {} stopped accepting bids {} second(s) ago; the next round has not yet begun",
-            round_id,
-            (env.block.time.seconds() - bet_round.open_time.seconds())
-        ))));
+    let bet_round = place_bet(
+        deps.storage,
+        &env,
+        &info.sender,
+        bet_round,
+        dir.clone(),
+        gross,
+    )?;
+    resp = resp
+        .add_attribute("action", "fuzio-claim-and-bet")
+        .add_event(
+            Claimed {
+                player: info.sender.clone(),
+                claimed_by: info.sender.clone(),
+                recipient: info.sender.clone(),
+                amount: amount_winnings,
+            }
+            .to_event(),
+        )
+        .add_event(
+            BetPlaced {
+                round_id: bet_round.id,
+                player: info.sender.clone(),
+                direction: dir,
+                amount: gross,
+                bull_amount: bet_round.bull_amount,
+                bear_amount: bet_round.bear_amount,
+            }
+            .to_event(),
+        );
+
+    let refund = amount_winnings - gross;
+    if !refund.is_zero() {
+        resp = resp
+            .add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(refund.u128(), &config.token_denom),
+            })
+            .add_attribute("refund", refund);
     }
 
-    let bet_info_key = bet_info_key(round_id.u128(), &info.sender.clone());
+    Ok(resp)
+}
 
-    let bet_info = bet_info_storage().may_load(deps.storage, bet_info_key.clone())?;
+/// Settles a single bet once its round has finished: the bet is removed and the
+/// claim recorded. Returns the amount owed to the player and the part of it the
+/// gaming fee applies to. Bets in rounds that haven't finished, or whose claim
+/// has expired, are left alone.
+fn settle_game(
+    storage: &mut dyn Storage,
+    config: &Config,
+    env: &Env,
+    game: &BetInfo,
+    claimed_by: &Addr,
+    recipient: &Addr,
+) -> StdResult<(Uint128, Uint128)> {
+    let round_id = game.round_id;
+    let round = match ROUNDS.may_load(storage, round_id.u128())? {
+        Some(round) => round,
+        None => return Ok((Uint128::zero(), Uint128::zero())),
+    };
 
-    if !bet_info.is_none() {
-        return Err(ContractError::Std(StdError::generic_err(format!(
-            "You have already bet for this game for {}, with amount: {}",
-            bet_info.clone().unwrap().direction.to_string(),
-            bet_info.unwrap().amount
-        ))));
+    if is_claim_expired(config, &round, env) {
+        return Ok((Uint128::zero(), Uint128::zero()));
     }
 
-    match dir {
-        Direction::Bull => {
-            bet_info_storage().save(
-                deps.storage,
-                bet_info_key.clone(),
-                &BetInfo {
-                    player: info.sender.clone(),
-                    round_id,
-                    amount: gross,
-                    direction: Direction::Bull,
-                },
-            )?;
-            bet_round.bull_amount += gross;
-            NEXT_ROUND.save(deps.storage, &bet_round)?;
-            resp = resp
-                .add_attribute("action", "fuzio-bet".to_string())
-                .add_attribute("round", round_id.to_string())
-                .add_attribute("direction", "bull".to_string())
-                .add_attribute("amount", gross.to_string())
-                .add_attribute("round_bear_total", bet_round.bear_amount.to_string())
-                .add_attribute("account", info.sender.to_string());
-        }
-        Direction::Bear => {
-            bet_info_storage().save(
-                deps.storage,
-                bet_info_key.clone(),
-                &BetInfo {
-                    player: info.sender.clone(),
-                    round_id,
-                    amount: gross,
-                    direction: Direction::Bear,
-                },
-            )?;
-            bet_round.bear_amount += gross;
-            NEXT_ROUND.save(deps.storage, &bet_round)?;
-            resp = resp
-                .add_attribute("action", "fuzio-bet".to_string())
-                .add_attribute("round", round_id.to_string())
-                .add_attribute("direction", "bear".to_string())
-                .add_attribute("amount", gross.to_string())
-                .add_attribute("round_bear_total", bet_round.bear_amount.to_string())
-                .add_attribute("account", info.sender.to_string());
-        }
+    bet_info_storage().remove(storage, bet_info_key(round_id.u128(), &game.player))?;
+
+    let (round_winnings, round_commissionable) = compute_game_winnings(&round, game);
+    if !is_game_scored(storage, &round, game)? {
+        score_game(storage, config, &round, game)?;
     }
 
-    Ok(resp)
+    if round_winnings > Uint128::zero() {
+        claim_info_storage().save(
+            storage,
+            claim_info_key(round_id.u128(), &game.player),
+            &ClaimInfo {
+                player: game.player.clone(),
+                round_id,
+                claimed_amount: round_winnings,
+                claimed_by: Some(claimed_by.clone()),
+                recipient: Some(recipient.clone()),
+            },
+        )?;
+    }
+
+    Ok((round_winnings, round_commissionable))
 }
 
-fn execute_close_round(
-    deps: DepsMut<SeiQueryWrapper>,
-    env: Env,
-) -> Result<Response, ContractError> {
-    assert_not_halted(deps.as_ref())?;
-    let now = env.block.time;
-    let config = CONFIG.load(deps.storage)?;
-    let mut resp: Response = Response::new();
+/// What became of a bet, for the player stats
+enum Outcome {
+    Won,
+    Lost,
+    Refunded,
+}
 
-    let maybe_live_round = LIVE_ROUND.may_load(deps.storage)?;
-    match &maybe_live_round {
-        Some(live_round) => {
-            if now >= live_round.close_time {
-                let finished_round = compute_round_close(deps.as_ref(), live_round)?;
-                ROUNDS.save(deps.storage, live_round.id.u128(), &finished_round)?;
-                resp = resp
-                    .add_attribute("action", "fuzio-finished-round")
-                    .add_attribute("round_id", live_round.id.to_string())
-                    .add_attribute("close_price", finished_round.close_price.to_string())
-                    .add_attribute(
-                        "winner",
-                        match finished_round.winner {
-                            Some(w) => w.to_string(),
-                            None => "everybody".to_string(),
-                        },
-                    );
-                LIVE_ROUND.remove(deps.storage);
-            }
-        }
-        None => {}
+fn game_outcome(round: &FinishedRound, game: &BetInfo) -> Outcome {
+    if round.voided || round.bear_amount.is_zero() || round.bull_amount.is_zero() {
+        return Outcome::Refunded;
     }
+    match &round.winner {
+        Some(winner) if *winner == game.direction => Outcome::Won,
+        Some(_) => Outcome::Lost,
+        None => Outcome::Refunded,
+    }
+}
 
-    /* Close the bidding round if it is finished
-     * NOTE Don't allow two live rounds at the same time - wait for the other to close
-     */
-    let new_bid_round = |deps: DepsMut<SeiQueryWrapper>, env: Env| -> StdResult<Uint128> {
-        let id = Uint128::from(NEXT_ROUND_ID.load(deps.storage)?);
-        let open_time = match LIVE_ROUND.may_load(deps.storage)? {
-            Some(live_round) => live_round.close_time,
-            None => env
-                .block
-                .time
-                .plus_seconds(config.next_round_seconds.u128() as u64),
-        };
-        let close_time = open_time.plus_seconds(config.next_round_seconds.u128() as u64);
+/// Whether a bet has been scored yet. The bets of a round are scored after
+/// it closes, a few at a time in the order of their players; those settled
+/// before are scored as they are. Rounds closed before 1.1.0 weren't scored,
+/// their bets are scored as they are settled.
+fn is_game_scored(storage: &dyn Storage, round: &FinishedRound, game: &BetInfo) -> StdResult<bool> {
+    if round.pricing.is_none() {
+        return Ok(false);
+    }
+    Ok(match UNSCORED_ROUNDS.may_load(storage, round.id.u128())? {
+        None => true,
+        Some(last_scored) => matches!(last_scored, Some(player) if game.player <= player),
+    })
+}
 
-        NEXT_ROUND.save(
-            deps.storage,
-            &NextRound {
-                bear_amount: Uint128::zero(),
-                bull_amount: Uint128::zero(),
-                bid_time: env.block.time,
-                close_time,
-                open_time,
-                id,
-            },
-        )?;
-        NEXT_ROUND_ID.save(deps.storage, &(id.u128() + 1u128))?;
-        Ok(id)
-    };
+/// Scores at most `limit` bets of the rounds that closed, oldest first, so
+/// losing bets count whether or not they are ever settled. Returns how many
+/// were scored and whether all have been.
+fn score_rounds(
+    storage: &mut dyn Storage,
+    config: &Config,
+    limit: usize,
+) -> StdResult<(usize, bool)> {
+    let rounds = UNSCORED_ROUNDS
+        .range(storage, None, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
 
-    let maybe_open_round = NEXT_ROUND.may_load(deps.storage)?;
-    match &maybe_open_round {
-        Some(open_round) => {
-            if LIVE_ROUND.may_load(deps.storage)?.is_none() && now >= open_round.open_time {
-                let live_round = compute_round_open(deps.as_ref(), env.clone(), open_round)?;
-                resp = resp
-                    .add_attribute("action", "fuzio-bidding-close")
-                    .add_attribute("round_id", live_round.id.to_string())
-                    .add_attribute("open_price", live_round.open_price.to_string())
-                    .add_attribute("bear_amount", live_round.bear_amount.to_string())
-                    .add_attribute("bull_amount", live_round.bull_amount.to_string());
-                LIVE_ROUND.save(deps.storage, &live_round)?;
-                NEXT_ROUND.remove(deps.storage);
-                let new_round_id = new_bid_round(deps, env)?;
-                resp = resp
-                    .add_attribute("action", "fuzio-new-round")
-                    .add_attribute("round_id", new_round_id);
-            }
+    let mut scored = 0;
+    for (round_id, mut last_scored) in rounds {
+        let round = ROUNDS.load(storage, round_id)?;
+        let left = limit - scored;
+
+        /* One more than can be scored tells whether any bets are left */
+        let games = bet_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id)
+            .range(
+                storage,
+                last_scored
+                    .as_ref()
+                    .map(|player| Bound::exclusive(bet_info_key(round_id, player))),
+                None,
+                Order::Ascending,
+            )
+            .take(left + 1)
+            .map(|res| res.map(|item| item.1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let games_left = games.len() > left;
+        for game in games.into_iter().take(left) {
+            score_game(storage, config, &round, &game)?;
+            last_scored = Some(game.player);
+            scored += 1;
         }
-        None => {
-            let new_round_id = new_bid_round(deps, env)?;
-            resp = resp
-                .add_attribute("action", "fuzio-new-round")
-                .add_attribute("round_id", new_round_id);
+
+        if games_left {
+            UNSCORED_ROUNDS.save(storage, round_id, &last_scored)?;
+            return Ok((scored, false));
         }
+        UNSCORED_ROUNDS.remove(storage, round_id);
     }
 
-    Ok(resp)
+    let done = UNSCORED_ROUNDS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    Ok((scored, done))
 }
 
-fn execute_update_config(
+fn execute_score_rounds(
     deps: DepsMut<SeiQueryWrapper>,
-    info: MessageInfo,
-    u_config: Config,
+    limit: Option<u32>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_SCORE_LIMIT).min(MAX_SCORE_LIMIT) as usize;
+    let (scored, done) = score_rounds(deps.storage, &config, limit)?;
 
-    CONFIG.save(deps.storage, &u_config)?;
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-score-rounds")
+        .add_attribute("scored", scored.to_string())
+        .add_attribute("done", done.to_string()))
+}
 
-    Ok(Response::new())
+/// Records the outcome of a bet and what it is owed net of the gaming fee
+fn score_game(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &FinishedRound,
+    game: &BetInfo,
+) -> StdResult<()> {
+    let (winnings, commissionable) = compute_game_winnings(round, game);
+    let fee = compute_gaming_fee(config, commissionable)?;
+    record_settlement(
+        storage,
+        round.close_time,
+        game,
+        game_outcome(round, game),
+        winnings - fee,
+        fee,
+    )?;
+    record_season_result(storage, round, game, winnings)
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps<SeiQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::Config {} => to_binary(&query_config(deps)?),
-        QueryMsg::Status {} => to_binary(&query_status(deps, env)?),
-        QueryMsg::MyCurrentPosition { address } => {
-            to_binary(&query_my_current_position(deps, address)?)
-        }
-        QueryMsg::FinishedRound { round_id } => to_binary(&query_finished_round(deps, round_id)?),
-        QueryMsg::MyGameList {
-            player,
-            start_after,
-            limit,
-        } => to_binary(&query_my_games(deps, player, start_after, limit)?),
-        QueryMsg::MyPendingReward { player } => to_binary(&query_my_pending_reward(deps, player)?),
-        QueryMsg::MyPendingRewardRounds { player } => {
-            to_binary(&query_my_pending_reward_rounds(deps, player)?)
+/// Records the outcome of a bet, `won` being what it is owed net of the
+/// gaming `fee`. It counts towards the leaderboard periods containing `time`.
+fn record_settlement(
+    storage: &mut dyn Storage,
+    time: Timestamp,
+    game: &BetInfo,
+    outcome: Outcome,
+    won: Uint128,
+    fee: Uint128,
+) -> StdResult<()> {
+    update_player_stats(storage, time, &game.player, |stats| {
+        stats.settled_wagered += game.amount;
+        stats.total_won += won;
+        stats.fees_paid += fee;
+        match outcome {
+            Outcome::Won => {
+                stats.rounds_won += 1;
+                stats.current_win_streak += 1;
+                stats.best_win_streak = stats.best_win_streak.max(stats.current_win_streak);
+            }
+            Outcome::Lost => {
+                stats.rounds_lost += 1;
+                stats.current_win_streak = 0;
+            }
+            Outcome::Refunded => stats.rounds_refunded += 1,
+        }
+    })?;
+
+    update_leaderboards(storage, time, &game.player, |score| {
+        score.settled_wagered += game.amount;
+        score.won += won;
+        score_settlement(score, &outcome);
+    })
+}
+
+/// Counts a bet towards the season its round closed in, if any. `winnings`
+/// is what the bet is owed before the gaming fee.
+fn record_season_result(
+    storage: &mut dyn Storage,
+    round: &FinishedRound,
+    game: &BetInfo,
+    winnings: Uint128,
+) -> StdResult<()> {
+    match season_at(storage, round.close_time)? {
+        Some(season) if !season.finalized && game.amount >= season.min_bet => {
+            let window = leaderboard_window(
+                &LeaderboardPeriod::Season {
+                    season_id: season.id,
+                },
+                round.close_time,
+            );
+            update_leaderboard_score(storage, window, &game.player, |score| {
+                score.volume += game.amount;
+                score.settled_wagered += game.amount;
+                score.won += winnings;
+                score_settlement(score, &game_outcome(round, game));
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn score_settlement(score: &mut LeaderboardScore, outcome: &Outcome) {
+    score.rounds += 1;
+    match outcome {
+        Outcome::Won => {
+            score.current_win_streak += 1;
+            score.best_win_streak = score.best_win_streak.max(score.current_win_streak);
+        }
+        Outcome::Lost => score.current_win_streak = 0,
+        Outcome::Refunded => {}
+    }
+}
+
+fn record_payout(
+    storage: &mut dyn Storage,
+    env: &Env,
+    paid: Uint128,
+    fee: Uint128,
+    denom: &str,
+) -> StdResult<()> {
+    update_global_stats(storage, env.block.time, |stats| {
+        add_coin(&mut stats.paid_out, denom, paid);
+        add_coin(&mut stats.fees, denom, fee);
+    })
+}
+
+/// Applies `update` to the player's stats, counting a player who had none
+/// among the players of the day containing `time`
+fn update_player_stats(
+    storage: &mut dyn Storage,
+    time: Timestamp,
+    player: &Addr,
+    update: impl FnOnce(&mut PlayerStats),
+) -> StdResult<()> {
+    let mut stats = match PLAYER_STATS.may_load(storage, player.clone())? {
+        Some(stats) => stats,
+        None => {
+            update_global_stats(storage, time, |stats| stats.unique_players += 1)?;
+            PlayerStats::default()
+        }
+    };
+    update(&mut stats);
+    PLAYER_STATS.save(storage, player.clone(), &stats)
+}
+
+/// Applies `update` to the totals and to the bucket of the day containing `time`
+fn update_global_stats(
+    storage: &mut dyn Storage,
+    time: Timestamp,
+    update: impl Fn(&mut GlobalStats),
+) -> StdResult<()> {
+    let mut totals = GLOBAL_STATS.may_load(storage)?.unwrap_or_default();
+    update(&mut totals);
+    GLOBAL_STATS.save(storage, &totals)?;
+
+    DAILY_STATS.update(storage, time.seconds() / DAY, |stats| -> StdResult<_> {
+        let mut stats = stats.unwrap_or_default();
+        update(&mut stats);
+        Ok(stats)
+    })?;
+    Ok(())
+}
+
+fn add_coin(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) {
+    if amount.is_zero() {
+        return;
+    }
+    match coins.iter_mut().find(|c| c.denom == denom) {
+        Some(c) => c.amount += amount,
+        None => coins.push(coin(amount.u128(), denom)),
+    }
+}
+
+const LEADERBOARD_PERIODS: [LeaderboardPeriod; 3] = [
+    LeaderboardPeriod::Daily,
+    LeaderboardPeriod::Weekly,
+    LeaderboardPeriod::AllTime,
+];
+
+/// The window of the period containing `time`
+fn leaderboard_window(period: &LeaderboardPeriod, time: Timestamp) -> String {
+    match period {
+        LeaderboardPeriod::Daily => format!("daily_{}", time.seconds() / DAY),
+        /* The epoch is a Thursday */
+        LeaderboardPeriod::Weekly => format!("weekly_{}", (time.seconds() + 3 * DAY) / (7 * DAY)),
+        LeaderboardPeriod::AllTime => "all_time".to_string(),
+        LeaderboardPeriod::Season { season_id } => format!("season_{}", season_id),
+    }
+}
+
+/// Applies `update` to the player's score in the window of every period
+/// containing `time`
+fn update_leaderboards(
+    storage: &mut dyn Storage,
+    time: Timestamp,
+    player: &Addr,
+    update: impl Fn(&mut LeaderboardScore),
+) -> StdResult<()> {
+    for period in LEADERBOARD_PERIODS.iter() {
+        let window = leaderboard_window(period, time);
+        update_leaderboard_score(storage, window, player, &update)?;
+    }
+    Ok(())
+}
+
+fn update_leaderboard_score(
+    storage: &mut dyn Storage,
+    window: String,
+    player: &Addr,
+    update: impl Fn(&mut LeaderboardScore),
+) -> StdResult<()> {
+    leaderboard_storage().update(
+        storage,
+        (window.clone(), player.clone()),
+        |score| -> StdResult<_> {
+            let mut score = score.unwrap_or(LeaderboardScore {
+                player: player.clone(),
+                window,
+                volume: Uint128::zero(),
+                settled_wagered: Uint128::zero(),
+                won: Uint128::zero(),
+                rounds: 0,
+                current_win_streak: 0,
+                best_win_streak: 0,
+            });
+            update(&mut score);
+            Ok(score)
+        },
+    )?;
+    Ok(())
+}
+
+/// Returns the amount a bet in a finished round is owed and the part of it the
+/// gaming fee applies to.
+fn compute_game_winnings(round: &FinishedRound, game: &BetInfo) -> (Uint128, Uint128) {
+    if round.voided || round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero()
+    {
+        /* Voided or one sided round, everybody gets their bet back free of fees */
+        return (game.amount, Uint128::zero());
+    }
+
+    let pool_shares = round.bear_amount + round.bull_amount;
+    let round_winnings = match (&round.winner, &game.direction) {
+        (Some(Direction::Bull), Direction::Bull) => {
+            pool_shares.multiply_ratio(game.amount, round.bull_amount)
+        }
+        (Some(Direction::Bear), Direction::Bear) => {
+            pool_shares.multiply_ratio(game.amount, round.bear_amount)
+        }
+        (Some(_), _) => Uint128::zero(),
+        (None, _) => game.amount,
+    };
+    (round_winnings, round_winnings)
+}
+
+/// When the winnings of a finished round can no longer be claimed, if ever.
+fn claim_expires_at(config: &Config, round: &FinishedRound) -> Option<Timestamp> {
+    config
+        .claim_expiry_seconds
+        .map(|expiry| round.close_time.plus_seconds(expiry.u128() as u64))
+}
+
+fn is_claim_expired(config: &Config, round: &FinishedRound, env: &Env) -> bool {
+    matches!(claim_expires_at(config, round), Some(expires_at) if env.block.time >= expires_at)
+}
+
+fn execute_sweep_expired(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    round_ids: Vec<Uint128>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+    assert_not_paused(deps.as_ref(), Pausable::Claims)?;
+    let mut left = limit.unwrap_or(DEFAULT_SWEEP_LIMIT).min(MAX_SWEEP_LIMIT) as usize;
+    let mut done = true;
+    let config = CONFIG.load(deps.storage)?;
+    let treasury = config
+        .treasury
+        .clone()
+        .ok_or(ContractError::NoTreasury {})?;
+    let mut swept = Uint128::zero();
+    let mut resp = Response::new();
+
+    for round_id in round_ids {
+        let round = ROUNDS.load(deps.storage, round_id.u128())?;
+        if !is_claim_expired(&config, &round, &env) {
+            return Err(ContractError::ClaimNotExpired { round_id });
+        }
+
+        /* One more than can be swept tells whether any bets are left */
+        let games = bet_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id.u128())
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(left + 1)
+            .map(|res| res.map(|item| item.1))
+            .collect::<StdResult<Vec<_>>>()?;
+        if games.len() > left {
+            done = false;
+        }
+
+        let mut round_swept = Uint128::zero();
+        for game in games.into_iter().take(left) {
+            left -= 1;
+            let (round_winnings, _) = compute_game_winnings(&round, &game);
+            round_swept += round_winnings;
+            if !is_game_scored(deps.storage, &round, &game)? {
+                score_game(deps.storage, &config, &round, &game)?;
+            }
+            bet_info_storage().remove(deps.storage, bet_info_key(round_id.u128(), &game.player))?;
+        }
+
+        swept += round_swept;
+        resp = resp.add_attribute(format!("round_{}", round_id), round_swept);
+        if !done {
+            break;
+        }
+    }
+
+    if !swept.is_zero() {
+        resp = resp.add_message(BankMsg::Send {
+            to_address: treasury.to_string(),
+            amount: coins(swept.u128(), &config.token_denom),
+        });
+    }
+
+    Ok(resp
+        .add_attribute("action", "fuzio-sweep-expired")
+        .add_attribute("treasury", treasury)
+        .add_attribute("amount", swept)
+        .add_attribute("done", done.to_string()))
+}
+
+fn execute_prune_history(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    before_round: Uint128,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+    let config = CONFIG.load(deps.storage)?;
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+
+    /* Rounds kept don't count towards the limit, but every round, bet and
+    claim looked at counts towards the scan */
+    let mut left = MAX_PRUNE_SCAN;
+    let rounds = ROUNDS
+        .range(
+            deps.storage,
+            start_after.map(|round_id| Bound::exclusive(round_id.u128())),
+            Some(Bound::exclusive(before_round.u128())),
+            Order::Ascending,
+        )
+        .take(left)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut pruned: Vec<String> = vec![];
+    let mut last_round = None;
+    for (round_id, round) in rounds {
+        if pruned.len() == limit || left == 0 {
+            break;
+        }
+        left -= 1;
+
+        /* Rounds still being scored are kept until they have been */
+        if UNSCORED_ROUNDS.has(deps.storage, round_id) {
+            last_round = Some(round_id);
+            continue;
+        }
+
+        /* One more than can be looked at tells whether any are left */
+        let games = bet_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(left + 1)
+            .map(|res| res.map(|item| item.1))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        /* Rounds with winnings left to claim or sweep are kept as they are */
+        if games
+            .iter()
+            .any(|game| !compute_game_winnings(&round, game).0.is_zero())
+        {
+            last_round = Some(round_id);
+            continue;
+        }
+
+        /* Only losing bets are looked at, settled like a claim would. When
+        there are more the round is carried on with next time. */
+        let games_left = games.len() > left;
+        for game in games.into_iter().take(left) {
+            left -= 1;
+            if !is_game_scored(deps.storage, &round, &game)? {
+                score_game(deps.storage, &config, &round, &game)?;
+            }
+            bet_info_storage().remove(deps.storage, bet_info_key(round_id, &game.player))?;
+        }
+        if games_left {
+            break;
+        }
+
+        let claims = claim_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id)
+            .range(deps.storage, None, None, Order::Ascending)
+            .take(left + 1)
+            .map(|res| res.map(|item| item.1))
+            .collect::<StdResult<Vec<_>>>()?;
+        let claims_left = claims.len() > left;
+        let mut claimed_amount = PRUNED_CLAIMS
+            .may_load(deps.storage, round_id)?
+            .unwrap_or_default();
+        for claim in claims.into_iter().take(left) {
+            left -= 1;
+            claimed_amount += claim.claimed_amount;
+            claim_info_storage().remove(deps.storage, claim_info_key(round_id, &claim.player))?;
+        }
+        if claims_left {
+            PRUNED_CLAIMS.save(deps.storage, round_id, &claimed_amount)?;
+            break;
+        }
+        PRUNED_CLAIMS.remove(deps.storage, round_id);
+
+        last_round = Some(round_id);
+        ARCHIVED_ROUNDS.save(
+            deps.storage,
+            round_id,
+            &ArchivedRound::new(&round, claimed_amount),
+        )?;
+        ROUNDS.remove(deps.storage, round_id);
+        pruned.push(round_id.to_string());
+    }
+
+    let mut resp = Response::new()
+        .add_attribute("action", "fuzio-prune-history")
+        .add_attribute("pruned_rounds", pruned.join(","));
+    if let Some(last_round) = last_round {
+        resp = resp.add_attribute("last_round", last_round.to_string());
+    }
+    Ok(resp)
+}
+
+/// Winnings go to the player unless they asked for them to be sent elsewhere.
+fn validate_recipient(
+    deps: Deps<SeiQueryWrapper>,
+    recipient: Option<Addr>,
+    player: &Addr,
+) -> StdResult<Addr> {
+    match recipient {
+        Some(recipient) => deps.api.addr_validate(recipient.as_str()),
+        None => Ok(player.clone()),
+    }
+}
+
+/// Takes the gaming fee on the commissionable winnings. The running season's
+/// share of it goes to its prize pool and the rest is split between the dev
+/// wallets.
+fn distribute_gaming_fee(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    amount_commissionable: Uint128,
+) -> StdResult<(Uint128, Vec<BankMsg>, FeesDistributed)> {
+    let gaming_fee = compute_gaming_fee(config, amount_commissionable)?;
+    let mut fees_distributed = FeesDistributed {
+        amount: gaming_fee,
+        denom: config.token_denom.clone(),
+        season_id: None,
+        season_amount: Uint128::zero(),
+    };
+
+    if let Some(mut season) = season_at(storage, env.block.time)? {
+        if season.denom == config.token_denom {
+            let season_amount = gaming_fee * season.fee_share;
+            season.prize_pool += season_amount;
+            SEASONS.save(storage, season.id, &season)?;
+            fees_distributed.season_id = Some(season.id);
+            fees_distributed.season_amount = season_amount;
+        }
+    }
+
+    let dev_fee = gaming_fee - fees_distributed.season_amount;
+    let messages_dev_fees = config
+        .dev_wallet_list
+        .iter()
+        .map(|dev_wallet| BankMsg::Send {
+            to_address: dev_wallet.address.to_string(),
+            amount: coins((dev_fee * dev_wallet.ratio).u128(), &config.token_denom),
+        })
+        .filter(|msg| !matches!(msg, BankMsg::Send { amount, .. } if amount[0].amount.is_zero()))
+        .collect();
+    Ok((gaming_fee, messages_dev_fees, fees_distributed))
+}
+
+/// The season running at `time`, if any
+fn season_at(storage: &dyn Storage, time: Timestamp) -> StdResult<Option<Season>> {
+    /* Seasons are in chronological order, so only those starting later are skipped */
+    for res in SEASONS.range(storage, None, None, Order::Descending) {
+        let (_, season) = res?;
+        if season.start_time <= time {
+            return Ok((time < season.end_time).then_some(season));
+        }
+    }
+    Ok(None)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_create_season(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    start_time: Timestamp,
+    end_time: Timestamp,
+    min_bet: Option<Uint128>,
+    min_rounds: Option<u64>,
+    fee_share: Decimal,
+    prize_curve: Vec<Decimal>,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::MarketCreator)?;
+    assert_not_emergency(deps.as_ref())?;
+    nonpayable(&info)?;
+
+    let invalid = |reason: &str| ContractError::InvalidSeason {
+        reason: reason.to_string(),
+    };
+    if end_time <= start_time {
+        return Err(invalid("end_time has to be after start_time"));
+    }
+    if end_time <= env.block.time {
+        return Err(invalid("end_time has already passed"));
+    }
+    if fee_share > Decimal::one() {
+        return Err(invalid("fee_share can't be more than 1"));
+    }
+    if prize_curve.is_empty() || prize_curve.len() > MAX_SEASON_WINNERS {
+        return Err(invalid(&format!(
+            "prize_curve needs between 1 and {} ranks",
+            MAX_SEASON_WINNERS
+        )));
+    }
+    if prize_curve.iter().any(Decimal::is_zero)
+        || prize_curve
+            .iter()
+            .fold(Decimal::zero(), |total, share| total + share)
+            != Decimal::one()
+    {
+        return Err(invalid(
+            "prize_curve shares have to be positive and add up to 1",
+        ));
+    }
+
+    if let Some(res) = SEASONS
+        .range(deps.storage, None, None, Order::Descending)
+        .next()
+    {
+        let (season_id, last) = res?;
+        if last.end_time > start_time {
+            return Err(ContractError::SeasonOverlap { season_id });
+        }
+    }
+
+    let id = NEXT_SEASON_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_SEASON_ID.save(deps.storage, &(id + 1))?;
+    let season = Season {
+        id,
+        start_time,
+        end_time,
+        min_bet: min_bet.unwrap_or_default(),
+        min_rounds: min_rounds.unwrap_or_default(),
+        fee_share,
+        prize_curve,
+        denom: CONFIG.load(deps.storage)?.token_denom,
+        prize_pool: Uint128::zero(),
+        finalized: false,
+        winners: vec![],
+    };
+    SEASONS.save(deps.storage, id, &season)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-create-season")
+        .add_attribute("season_id", id.to_string())
+        .add_attribute("start_time", start_time.to_string())
+        .add_attribute("end_time", end_time.to_string()))
+}
+
+fn execute_fund_season(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    season_id: u64,
+) -> Result<Response, ContractError> {
+    let mut season = SEASONS.load(deps.storage, season_id)?;
+    if season.finalized {
+        return Err(ContractError::SeasonFinalized { season_id });
+    }
+
+    let funds_sent = one_coin(&info)?;
+    if funds_sent.denom != season.denom {
+        return Err(ContractError::InvalidFunds {});
+    }
+    season.prize_pool += funds_sent.amount;
+    SEASONS.save(deps.storage, season_id, &season)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-fund-season")
+        .add_attribute("season_id", season_id.to_string())
+        .add_attribute("sponsor", info.sender)
+        .add_attribute("amount", funds_sent.amount)
+        .add_attribute("prize_pool", season.prize_pool))
+}
+
+/**
+ * Pays the prize pool out to the best net PnLs among the players with enough
+ * bets in the season's rounds, following the prize curve. When nobody qualified, the pool
+ * goes to the treasury.
+ */
+fn execute_finalize_season(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    season_id: u64,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::MarketCreator)?;
+    let mut season = SEASONS.load(deps.storage, season_id)?;
+    if season.finalized {
+        return Err(ContractError::SeasonFinalized { season_id });
+    }
+    if env.block.time < season.end_time {
+        return Err(ContractError::SeasonNotEnded {
+            season_id,
+            end_time: season.end_time,
+        });
+    }
+    /* Its rounds are scored after they close, so they all have to be closed
+    and scored. Rounds close in order, the oldest unscored one is enough. */
+    let live_close = LIVE_ROUND
+        .may_load(deps.storage)?
+        .map(|round| round.close_time);
+    let next_close = NEXT_ROUND
+        .may_load(deps.storage)?
+        .map(|round| round.close_time);
+    if [live_close, next_close]
+        .into_iter()
+        .flatten()
+        .any(|close_time| close_time < season.end_time)
+    {
+        return Err(ContractError::SeasonRoundsOpen { season_id });
+    }
+    if let Some(round_id) = UNSCORED_ROUNDS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?
+    {
+        if ROUNDS.load(deps.storage, round_id)?.close_time < season.end_time {
+            return Err(ContractError::SeasonRoundsUnscored { season_id });
+        }
+    }
+
+    let window = leaderboard_window(&LeaderboardPeriod::Season { season_id }, season.end_time);
+    let min_rounds = season.min_rounds;
+    let winners = leaderboard_storage()
+        .idx
+        .net_pnl
+        .sub_prefix(window)
+        .range(deps.storage, None, None, Order::Descending)
+        .take(MAX_SEASON_RANKING_SCAN)
+        .filter(|res| !matches!(res, Ok((_, score)) if score.rounds < min_rounds))
+        .take(season.prize_curve.len())
+        .map(|res| res.map(|(_, score)| score.player))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut resp = Response::new();
+    if winners.is_empty() {
+        if !season.prize_pool.is_zero() {
+            let treasury = CONFIG
+                .load(deps.storage)?
+                .treasury
+                .ok_or(ContractError::NoTreasury {})?;
+            resp = resp
+                .add_message(BankMsg::Send {
+                    to_address: treasury.to_string(),
+                    amount: coins(season.prize_pool.u128(), &season.denom),
+                })
+                .add_attribute("treasury", treasury);
+        }
+    } else {
+        /* The shares of the ranks nobody qualified for are spread pro rata */
+        let shares = &season.prize_curve[..winners.len()];
+        let total_share = shares
+            .iter()
+            .fold(Decimal::zero(), |total, share| total + share);
+        let mut paid = Uint128::zero();
+        for (i, (player, share)) in winners.into_iter().zip(shares).enumerate() {
+            /* The last winner gets the rounding dust */
+            let prize = if i + 1 == shares.len() {
+                season.prize_pool - paid
+            } else {
+                season
+                    .prize_pool
+                    .multiply_ratio(share.atomics(), total_share.atomics())
+            };
+            paid += prize;
+
+            if !prize.is_zero() {
+                resp = resp.add_message(BankMsg::Send {
+                    to_address: player.to_string(),
+                    amount: coins(prize.u128(), &season.denom),
+                });
+            }
+            let rank = i as u32 + 1;
+            resp = resp.add_event(
+                Event::new("fuzio_season_prize")
+                    .add_attribute("season_id", season_id.to_string())
+                    .add_attribute("rank", rank.to_string())
+                    .add_attribute("player", &player)
+                    .add_attribute("prize", prize),
+            );
+            season.winners.push(SeasonPrize {
+                rank,
+                player,
+                prize,
+            });
+        }
+    }
+
+    season.finalized = true;
+    SEASONS.save(deps.storage, season_id, &season)?;
+
+    Ok(resp
+        .add_attribute("action", "fuzio-finalize-season")
+        .add_attribute("season_id", season_id.to_string())
+        .add_attribute("prize_pool", season.prize_pool))
+}
+
+fn execute_bet(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    round_id: Uint128,
+    dir: Direction,
+    gross: Uint128,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref(), Pausable::Betting)?;
+    assert_not_emergency(deps.as_ref())?;
+
+    let bet_round = assert_is_current_round(deps.as_ref(), round_id)?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let funds_sent = one_coin(&info)?;
+
+    if funds_sent.denom != config.token_denom {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    if funds_sent.amount != gross {
+        return Err(ContractError::NotEnoughFunds {});
+    }
+
+    let bet_round = place_bet(
+        deps.storage,
+        &env,
+        &info.sender,
+        bet_round,
+        dir.clone(),
+        gross,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-bet")
+        .add_event(
+            BetPlaced {
+                round_id,
+                player: info.sender,
+                direction: dir,
+                amount: gross,
+                bull_amount: bet_round.bull_amount,
+                bear_amount: bet_round.bear_amount,
+            }
+            .to_event(),
+        ))
+}
+
+/// Records a bet on the round open for bids and adds it to the round's pool.
+fn place_bet(
+    storage: &mut dyn Storage,
+    env: &Env,
+    player: &Addr,
+    mut bet_round: NextRound,
+    dir: Direction,
+    gross: Uint128,
+) -> Result<NextRound, ContractError> {
+    let round_id = bet_round.id;
+
+    if env.block.time > bet_round.open_time {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "Round {} stopped accepting bids {} second(s) ago; the next round has not yet begun",
+            round_id,
+            (env.block.time.seconds() - bet_round.open_time.seconds())
+        ))));
+    }
+
+    let bet_info_key = bet_info_key(round_id.u128(), player);
+
+    if let Some(bet_info) = bet_info_storage().may_load(storage, bet_info_key.clone())? {
+        return Err(ContractError::Std(StdError::generic_err(format!(
+            "You have already bet for this game for {}, with amount: {}",
+            bet_info.direction, bet_info.amount
+        ))));
+    }
+
+    TOTALS_SPENT.update(storage, player.clone(), |totals| -> StdResult<_> {
+        Ok(totals.unwrap_or_default() + gross)
+    })?;
+    let denom = CONFIG.load(storage)?.token_denom;
+    update_global_stats(storage, env.block.time, |stats| {
+        add_coin(&mut stats.volume, &denom, gross)
+    })?;
+    update_player_stats(storage, env.block.time, player, |stats| {
+        stats.rounds_played += 1;
+        stats.total_wagered += gross;
+    })?;
+    update_leaderboards(storage, env.block.time, player, |score| {
+        score.volume += gross
+    })?;
+
+    bet_info_storage().save(
+        storage,
+        bet_info_key,
+        &BetInfo {
+            player: player.clone(),
+            round_id,
+            amount: gross,
+            direction: dir.clone(),
+        },
+    )?;
+    match dir {
+        Direction::Bull => bet_round.bull_amount += gross,
+        Direction::Bear => bet_round.bear_amount += gross,
+    }
+    NEXT_ROUND.save(storage, &bet_round)?;
+
+    Ok(bet_round)
+}
+
+fn execute_close_round(
+    mut deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref(), Pausable::Rounds)?;
+    assert_not_emergency(deps.as_ref())?;
+    let now = env.block.time;
+    let config = CONFIG.load(deps.storage)?;
+    let mut resp: Response = Response::new();
+
+    let maybe_live_round = LIVE_ROUND.may_load(deps.storage)?;
+    if let Some(live_round) = &maybe_live_round {
+        if now >= live_round.close_time {
+            let read = get_current_price(deps.as_ref(), &env)?;
+            let finished_round = match read.price {
+                Some(price) => {
+                    record_price_sample(deps.storage, &env, price, |sample| {
+                        sample.closed_round = Some(live_round.id)
+                    })?;
+                    let close_price = compute_settlement_price(
+                        deps.storage,
+                        &env,
+                        &config,
+                        live_round.open_time,
+                        price,
+                    )?;
+                    compute_round_close(&config, live_round, close_price)
+                }
+                /* Nobody can tell who won */
+                None => void_round(compute_round_close(&config, live_round, Decimal::zero())),
+            };
+            let event = finish_round(deps.storage, &env, finished_round, read.source_prices)?;
+            resp = resp
+                .add_attribute("action", "fuzio-finished-round")
+                .add_event(event);
+            LIVE_ROUND.remove(deps.storage);
+            record_amm_observations(deps.branch(), &env, &config)?;
+        }
+    }
+
+    /* Close the bidding round if it is finished
+     * NOTE Don't allow two live rounds at the same time - wait for the other to close
+     */
+    let new_bid_round = |deps: DepsMut<SeiQueryWrapper>, env: Env| -> StdResult<Uint128> {
+        let id = Uint128::from(NEXT_ROUND_ID.load(deps.storage)?);
+        let open_time = match LIVE_ROUND.may_load(deps.storage)? {
+            Some(live_round) => live_round.close_time,
+            None => env
+                .block
+                .time
+                .plus_seconds(config.next_round_seconds.u128() as u64),
+        };
+        let close_time = open_time.plus_seconds(config.next_round_seconds.u128() as u64);
+
+        NEXT_ROUND.save(
+            deps.storage,
+            &NextRound {
+                bear_amount: Uint128::zero(),
+                bull_amount: Uint128::zero(),
+                bid_time: env.block.time,
+                close_time,
+                open_time,
+                id,
+            },
+        )?;
+        NEXT_ROUND_ID.save(deps.storage, &(id.u128() + 1u128))?;
+        Ok(id)
+    };
+
+    let maybe_open_round = NEXT_ROUND.may_load(deps.storage)?;
+    match &maybe_open_round {
+        Some(open_round) => {
+            if LIVE_ROUND.may_load(deps.storage)?.is_none() && now >= open_round.open_time {
+                let read = get_current_price(deps.as_ref(), &env)?;
+                match read.price {
+                    Some(price) => {
+                        record_price_sample(deps.storage, &env, price, |sample| {
+                            sample.opened_round = Some(open_round.id)
+                        })?;
+                        let open_price = compute_settlement_price(
+                            deps.storage,
+                            &env,
+                            &config,
+                            open_round.bid_time,
+                            price,
+                        )?;
+                        let live_round = compute_round_open(&config, &env, open_round, open_price);
+                        resp = resp
+                            .add_attribute("action", "fuzio-bidding-close")
+                            .add_event(
+                                RoundLocked {
+                                    round_id: live_round.id,
+                                    open_price: live_round.open_price,
+                                    close_time: live_round.close_time,
+                                    bull_amount: live_round.bull_amount,
+                                    bear_amount: live_round.bear_amount,
+                                }
+                                .to_event(),
+                            );
+                        LIVE_ROUND.save(deps.storage, &live_round)?;
+                    }
+                    /* The round can't start without an open price */
+                    None => {
+                        let live_round =
+                            compute_round_open(&config, &env, open_round, Decimal::zero());
+                        let finished_round = FinishedRound {
+                            close_time: env.block.time,
+                            ..void_round(compute_round_close(&config, &live_round, Decimal::zero()))
+                        };
+                        let event =
+                            finish_round(deps.storage, &env, finished_round, read.source_prices)?;
+                        resp = resp
+                            .add_attribute("action", "fuzio-finished-round")
+                            .add_event(event);
+                        record_amm_observations(deps.branch(), &env, &config)?;
+                    }
+                }
+                NEXT_ROUND.remove(deps.storage);
+                let new_round_id = new_bid_round(deps.branch(), env.clone())?;
+                resp = resp
+                    .add_attribute("action", "fuzio-new-round")
+                    .add_attribute("round_id", new_round_id);
+            }
+        }
+        None => {
+            let new_round_id = new_bid_round(deps.branch(), env.clone())?;
+            resp = resp
+                .add_attribute("action", "fuzio-new-round")
+                .add_attribute("round_id", new_round_id);
+        }
+    }
+
+    /* A busy round is scored over the next closes, or with ScoreRounds */
+    score_rounds(deps.storage, &config, CLOSE_SCORE_LIMIT)?;
+
+    Ok(resp)
+}
+
+fn execute_update_config(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    assert_can_change_config(deps.as_ref(), &info.sender, &update)?;
+    propose_config_update(deps, env, info.sender, update)
+}
+
+/// Where the fees go is up to the fee managers, the rest of the config to the
+/// config managers
+fn assert_can_change_config(
+    deps: Deps<SeiQueryWrapper>,
+    sender: &Addr,
+    update: &ConfigUpdate,
+) -> Result<(), ContractError> {
+    let clear = update.clear.clone().unwrap_or_default();
+    let fees = update.dev_wallet_list.is_some()
+        || update.treasury.is_some()
+        || clear.contains(&OptionalConfigField::Treasury);
+    let others = ConfigUpdate {
+        dev_wallet_list: None,
+        treasury: None,
+        clear: None,
+        ..update.clone()
+    } != ConfigUpdate::default()
+        || clear
+            .iter()
+            .any(|field| *field != OptionalConfigField::Treasury);
+
+    if fees {
+        assert_has_role(deps, sender, Role::FeeManager)?;
+    }
+    if others || !fees {
+        assert_has_role(deps, sender, Role::ConfigManager)?;
+    }
+    Ok(())
+}
+
+/// Applies a config update right away, or queues it when there is a timelock
+fn propose_config_update(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    proposer: Addr,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    /* Check it now so a bad change doesn't sit in the queue */
+    let new_config = apply_config_update(config.clone(), update.clone());
+    validate_config(deps.as_ref(), &env, &new_config)?;
+
+    let timelock = config.config_timelock_seconds.unwrap_or_default();
+    if timelock.is_zero() {
+        CONFIG.save(deps.storage, &new_config)?;
+        return Ok(Response::new()
+            .add_attribute("action", "fuzio-update-config")
+            .add_event(
+                ConfigChanged {
+                    id: None,
+                    changes: update,
+                }
+                .to_event(),
+            ));
+    }
+
+    let id = NEXT_CONFIG_CHANGE_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let change = PendingConfigChange {
+        id,
+        config: update,
+        proposer,
+        executable_at: env.block.time.plus_seconds(timelock.u128() as u64),
+    };
+    PENDING_CONFIG_CHANGES.save(deps.storage, id, &change)?;
+    NEXT_CONFIG_CHANGE_ID.save(deps.storage, &(id + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-queue-config-change")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", change.proposer)
+        .add_attribute("executable_at", change.executable_at.seconds().to_string())
+        .add_attributes(config_update_attributes(&change.config)))
+}
+
+fn execute_config_change(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let change = PENDING_CONFIG_CHANGES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoConfigChange { id })?;
+
+    if env.block.time < change.executable_at {
+        return Err(ContractError::ConfigChangeTimelocked {
+            id,
+            executable_at: change.executable_at,
+        });
+    }
+
+    /* Other changes may have been applied since it was queued */
+    let config = CONFIG.load(deps.storage)?;
+    let new_config = apply_config_update(config, change.config.clone());
+    validate_config(deps.as_ref(), &env, &new_config)?;
+
+    CONFIG.save(deps.storage, &new_config)?;
+    PENDING_CONFIG_CHANGES.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-update-config")
+        .add_event(
+            ConfigChanged {
+                id: Some(id),
+                changes: change.config,
+            }
+            .to_event(),
+        ))
+}
+
+/// Applies the fields set in the update
+fn apply_config_update(mut config: Config, update: ConfigUpdate) -> Config {
+    for field in update.clear.unwrap_or_default() {
+        match field {
+            OptionalConfigField::ClaimExpirySeconds => config.claim_expiry_seconds = None,
+            OptionalConfigField::Treasury => config.treasury = None,
+            OptionalConfigField::ConfigTimelockSeconds => config.config_timelock_seconds = None,
+            OptionalConfigField::PriceTwapSamples => config.price_twap_samples = None,
+            OptionalConfigField::PriceSource => config.price_source = None,
+        }
+    }
+    if let Some(next_round_seconds) = update.next_round_seconds {
+        config.next_round_seconds = next_round_seconds;
+    }
+    if let Some(minimum_bet) = update.minimum_bet {
+        config.minimum_bet = minimum_bet;
+    }
+    if let Some(gaming_fee) = update.gaming_fee {
+        config.gaming_fee = gaming_fee;
+    }
+    if let Some(token_denom) = update.token_denom {
+        config.token_denom = token_denom;
+    }
+    if let Some(bet_token_denom) = update.bet_token_denom {
+        config.bet_token_denom = bet_token_denom;
+    }
+    if let Some(dev_wallet_list) = update.dev_wallet_list {
+        config.dev_wallet_list = dev_wallet_list;
+    }
+    if let Some(claim_expiry_seconds) = update.claim_expiry_seconds {
+        config.claim_expiry_seconds = Some(claim_expiry_seconds);
+    }
+    if let Some(treasury) = update.treasury {
+        config.treasury = Some(treasury);
+    }
+    if let Some(config_timelock_seconds) = update.config_timelock_seconds {
+        config.config_timelock_seconds = Some(config_timelock_seconds);
+    }
+    if let Some(price_twap_samples) = update.price_twap_samples {
+        config.price_twap_samples = Some(price_twap_samples);
+    }
+    if let Some(price_source) = update.price_source {
+        config.price_source = Some(price_source);
+    }
+
+    config
+}
+
+/// Checks the game can run with this config
+fn validate_config(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+) -> Result<(), ContractError> {
+    if config.next_round_seconds.is_zero() {
+        return Err(ContractError::ZeroDuration {
+            field: "next_round_seconds".to_string(),
+        });
+    }
+    if config.claim_expiry_seconds == Some(Uint128::zero()) {
+        return Err(ContractError::ZeroDuration {
+            field: "claim_expiry_seconds".to_string(),
+        });
+    }
+    if config.gaming_fee > Uint128::from(MAX_GAMING_FEE) {
+        return Err(ContractError::GamingFeeTooHigh {
+            max: Uint128::from(MAX_GAMING_FEE),
+        });
+    }
+    if config.token_denom.is_empty() {
+        return Err(ContractError::EmptyDenom {});
+    }
+    if matches!(config.price_twap_samples, Some(samples) if !(2..=MAX_TWAP_SAMPLES).contains(&samples))
+    {
+        return Err(ContractError::InvalidTwapSamples {
+            max: MAX_TWAP_SAMPLES,
+        });
+    }
+    validate_dev_wallets(deps, &config.dev_wallet_list)?;
+    if let Some(treasury) = &config.treasury {
+        deps.api.addr_validate(treasury.as_str())?;
+    }
+    validate_price_source(deps.api, &price_source(config), false)?;
+    for source in checked_price_sources(price_source(config)) {
+        read_source(deps, env, config, &source)?;
+    }
+
+    Ok(())
+}
+
+fn validate_dev_wallets(
+    deps: Deps<SeiQueryWrapper>,
+    dev_wallets: &[WalletInfo],
+) -> Result<(), ContractError> {
+    let mut total_ratio = Decimal::zero();
+    for dev_wallet in dev_wallets {
+        deps.api.addr_validate(dev_wallet.address.as_str())?;
+        total_ratio += dev_wallet.ratio;
+    }
+
+    if total_ratio != Decimal::one() {
+        return Err(ContractError::WrongRatio {});
+    }
+
+    Ok(())
+}
+
+fn execute_cancel_config_change(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let change = PENDING_CONFIG_CHANGES
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::NoConfigChange { id })?;
+    assert_can_change_config(deps.as_ref(), &info.sender, &change.config)?;
+    PENDING_CONFIG_CHANGES.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-cancel-config-change")
+        .add_attribute("id", id.to_string())
+        .add_attribute("cancelled_by", info.sender))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.id {
+        REPLY_CLOSE_ROUND => execute_close_round(deps, env),
+        REPLY_CHECKPOINT_PRICE => checkpoint_price(deps, env),
+        id => Err(ContractError::Std(StdError::generic_err(format!(
+            "Unknown reply id {}",
+            id
+        )))),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<SeiQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Status {} => to_binary(&query_status(deps, env)?),
+        QueryMsg::FeederPrices { market } => to_binary(&query_feeder_prices(deps, market)?),
+        QueryMsg::PriceHistory { start_after, limit } => {
+            to_binary(&query_price_history(deps, start_after, limit)?)
+        }
+        QueryMsg::Odds { round_id } => to_binary(&query_odds(deps, round_id)?),
+        QueryMsg::SimulateBet {
+            round_id,
+            direction,
+            amount,
+        } => to_binary(&query_simulate_bet(deps, round_id, direction, amount)?),
+        QueryMsg::PauseState {} => to_binary(&PAUSE_STATE.load(deps.storage)?),
+        QueryMsg::Emergency {} => to_binary(&EmergencyResponse {
+            emergency: EMERGENCY.may_load(deps.storage)?,
+        }),
+        QueryMsg::MyCurrentPosition { address } => {
+            to_binary(&query_my_current_position(deps, address)?)
+        }
+        QueryMsg::FinishedRound { round_id } => to_binary(&query_finished_round(deps, round_id)?),
+        QueryMsg::Rounds {
+            start_after,
+            limit,
+            order,
+        } => to_binary(&query_rounds(deps, start_after, limit, order)?),
+        QueryMsg::LatestRounds { count } => {
+            to_binary(&query_rounds(deps, None, Some(count), Some(OrderBy::Desc))?)
+        }
+        QueryMsg::MyGameList {
+            player,
+            start_after,
+            limit,
+        } => to_binary(&query_my_games(deps, player, start_after, limit)?),
+        QueryMsg::MyPendingReward { player } => {
+            to_binary(&query_my_pending_reward(deps, env, player)?)
+        }
+        QueryMsg::MyPendingRewardRounds { player } => {
+            to_binary(&query_my_pending_reward_rounds(deps, env, player)?)
         }
         QueryMsg::GetUsersPerRound {
             round_id,
@@ -573,7 +1925,7 @@ pub fn query(deps: Deps<SeiQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<
             limit,
         } => to_binary(&query_users_per_round(deps, round_id, start_after, limit)?),
         QueryMsg::MyPendingRewardRound { round_id, player } => {
-            to_binary(&query_my_pending_reward_round(deps, round_id, player)?)
+            to_binary(&query_my_pending_reward_round(deps, env, round_id, player)?)
         }
         QueryMsg::GetClaimInfoPerRound {
             round_id,
@@ -591,7 +1943,88 @@ pub fn query(deps: Deps<SeiQueryWrapper>, env: Env, msg: QueryMsg) -> StdResult<
             limit,
         } => to_binary(&query_claim_info_by_user(deps, player, start_after, limit)?),
         QueryMsg::TotalSpent { player } => to_binary(&query_total_spent(deps, player)?),
+        QueryMsg::PlayerStats { player } => to_binary(&query_player_stats(deps, player)?),
+        QueryMsg::Leaderboard {
+            metric,
+            period,
+            limit,
+            at,
+        } => to_binary(&query_leaderboard(deps, env, metric, period, limit, at)?),
+        QueryMsg::GlobalStats { days } => to_binary(&query_global_stats(deps, env, days)?),
+        QueryMsg::Season { season_id } => to_binary(&SEASONS.load(deps.storage, season_id)?),
+        QueryMsg::Seasons { start_after, limit } => {
+            to_binary(&query_seasons(deps, start_after, limit)?)
+        }
         QueryMsg::GetAdmins {} => to_binary(&query_get_admins(deps)?),
+        QueryMsg::Roles { address } => to_binary(&query_roles(deps, address)?),
+        QueryMsg::PendingConfigChanges {} => to_binary(&query_pending_config_changes(deps)?),
+        QueryMsg::OwnershipProposal { candidate } => {
+            to_binary(&query_ownership_proposal(deps, candidate)?)
+        }
+        QueryMsg::ClaimExpiry { round_id } => to_binary(&query_claim_expiry(deps, env, round_id)?),
+        QueryMsg::Governance {} => to_binary(&GovernanceResponse {
+            governance: GOVERNANCE.may_load(deps.storage)?,
+        }),
+    }
+}
+
+fn query_rounds(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<RoundsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let order = match order {
+        Some(OrderBy::Desc) => Order::Descending,
+        _ => Order::Ascending,
+    };
+
+    let rounds = range_finished_rounds(deps.storage, start_after.map(|id| id.u128()), order)
+        .take(limit)
+        .map(|res| res.map(summarize_round))
+        .collect::<StdResult<Vec<_>>>()?;
+    let wins = |direction: Option<Direction>| {
+        rounds
+            .iter()
+            .filter(|summary| !summary.round.voided && summary.round.winner == direction)
+            .count() as u32
+    };
+
+    Ok(RoundsResponse {
+        bull_wins: wins(Some(Direction::Bull)),
+        bear_wins: wins(Some(Direction::Bear)),
+        draws: wins(None),
+        voided: rounds.iter().filter(|summary| summary.round.voided).count() as u32,
+        rounds,
+    })
+}
+
+fn summarize_round(round: FinishedRound) -> RoundSummary {
+    let total_amount = round.bull_amount + round.bear_amount;
+    let price_ratio = if round.open_price.is_zero() {
+        Decimal::one()
+    } else {
+        round.close_price / round.open_price
+    };
+    /* Same rules as the claims */
+    let winning_pool = match round.winner {
+        Some(Direction::Bull) => round.bull_amount,
+        Some(Direction::Bear) => round.bear_amount,
+        None => Uint128::zero(),
+    };
+    let payout_multiplier =
+        if round.bull_amount.is_zero() || round.bear_amount.is_zero() || winning_pool.is_zero() {
+            Decimal::one()
+        } else {
+            Decimal::from_ratio(total_amount, winning_pool)
+        };
+
+    RoundSummary {
+        round,
+        total_amount,
+        price_ratio,
+        payout_multiplier,
     }
 }
 
@@ -599,8 +2032,8 @@ fn query_finished_round(
     deps: Deps<SeiQueryWrapper>,
     round_id: Uint128,
 ) -> StdResult<FinishedRound> {
-    let round = ROUNDS.load(deps.storage, round_id.u128())?;
-    Ok(round)
+    may_load_finished_round(deps.storage, round_id.u128())?
+        .ok_or_else(|| StdError::not_found("fuzio_bet::fuzio_prediction_game::FinishedRound"))
 }
 
 fn query_my_current_position(
@@ -615,16 +2048,15 @@ fn query_my_current_position(
     let mut next_bull_amount = Uint128::zero();
     let mut next_bear_amount = Uint128::zero();
 
-    match next_bet_info {
-        Some(bet_info) => match bet_info.direction {
+    if let Some(bet_info) = next_bet_info {
+        match bet_info.direction {
             Direction::Bull => {
                 next_bull_amount = bet_info.amount;
             }
             Direction::Bear => {
                 next_bear_amount = bet_info.amount;
             }
-        },
-        None => {}
+        }
     }
 
     let mut live_bull_amount: Uint128 = Uint128::zero();
@@ -632,36 +2064,118 @@ fn query_my_current_position(
     if round_id > 1 {
         let live_bet_key = (round_id - 2, deps.api.addr_validate(&address)?);
         let live_bet_info = bet_info_storage().may_load(deps.storage, live_bet_key)?;
-        match live_bet_info {
-            Some(bet_info) => match bet_info.direction {
+        if let Some(bet_info) = live_bet_info {
+            match bet_info.direction {
                 Direction::Bull => {
                     live_bull_amount = bet_info.amount;
                 }
                 Direction::Bear => {
                     live_bear_amount = bet_info.amount;
                 }
-            },
-            None => {}
+            }
         }
     }
 
-    Ok(MyCurrentPositionResponse {
-        next_bear_amount,
-        next_bull_amount,
-        live_bear_amount,
-        live_bull_amount,
+    Ok(MyCurrentPositionResponse {
+        next_bear_amount,
+        next_bull_amount,
+        live_bear_amount,
+        live_bull_amount,
+    })
+}
+
+fn query_status(deps: Deps<SeiQueryWrapper>, env: Env) -> StdResult<StatusResponse> {
+    let live_round = LIVE_ROUND.may_load(deps.storage)?;
+    let bidding_round = NEXT_ROUND.may_load(deps.storage)?;
+    let current_time = env.block.time;
+
+    Ok(StatusResponse {
+        bidding_round,
+        live_round,
+        current_time,
+    })
+}
+
+fn query_odds(deps: Deps<SeiQueryWrapper>, round_id: Uint128) -> StdResult<OddsResponse> {
+    let round = assert_is_current_round(deps, round_id)?;
+    compute_odds(deps, &round)
+}
+
+/// Payout multipliers for the pools as they are, the same way claims are paid
+fn compute_odds(deps: Deps<SeiQueryWrapper>, round: &NextRound) -> StdResult<OddsResponse> {
+    let gaming_fee = CONFIG.load(deps.storage)?.gaming_fee;
+    let after_fee =
+        Decimal::one() - Decimal::from_ratio(gaming_fee, Uint128::from(FEE_PRECISION * 100));
+    let multiplier = |own: Uint128, other: Uint128| {
+        if own.is_zero() {
+            None
+        } else if other.is_zero() {
+            /* One sided rounds are refunded free of fees */
+            Some(Decimal::one())
+        } else {
+            Some(Decimal::from_ratio(own + other, own) * after_fee)
+        }
+    };
+
+    Ok(OddsResponse {
+        round_id: round.id,
+        bull_amount: round.bull_amount,
+        bear_amount: round.bear_amount,
+        bull_multiplier: multiplier(round.bull_amount, round.bear_amount),
+        bear_multiplier: multiplier(round.bear_amount, round.bull_amount),
     })
 }
 
-fn query_status(deps: Deps<SeiQueryWrapper>, env: Env) -> StdResult<StatusResponse> {
-    let live_round = LIVE_ROUND.may_load(deps.storage)?;
-    let bidding_round = NEXT_ROUND.may_load(deps.storage)?;
-    let current_time = env.block.time;
+fn query_simulate_bet(
+    deps: Deps<SeiQueryWrapper>,
+    round_id: Uint128,
+    direction: Direction,
+    amount: Uint128,
+) -> StdResult<SimulateBetResponse> {
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Can't simulate a bet of 0"));
+    }
+    let round = assert_is_current_round(deps, round_id)?;
+    let odds_before = compute_odds(deps, &round)?;
 
-    Ok(StatusResponse {
-        bidding_round,
-        live_round,
-        current_time,
+    let mut after = round.clone();
+    match direction {
+        Direction::Bull => after.bull_amount += amount,
+        Direction::Bear => after.bear_amount += amount,
+    }
+    let odds_after = compute_odds(deps, &after)?;
+
+    /* Settle it as if the round closed in the bet's favour */
+    let finished = FinishedRound {
+        id: after.id,
+        bid_time: after.bid_time,
+        open_time: after.open_time,
+        close_time: after.close_time,
+        open_price: Decimal::zero(),
+        close_price: Decimal::zero(),
+        winner: Some(direction.clone()),
+        bull_amount: after.bull_amount,
+        bear_amount: after.bear_amount,
+        pricing: None,
+        voided: false,
+    };
+    let bet = BetInfo {
+        player: Addr::unchecked(""),
+        round_id,
+        amount,
+        direction: direction.clone(),
+    };
+    let (winnings, commissionable) = compute_game_winnings(&finished, &bet);
+    let gaming_fee = compute_gaming_fee(&CONFIG.load(deps.storage)?, commissionable)?;
+
+    Ok(SimulateBetResponse {
+        round_id,
+        direction,
+        amount,
+        payout: winnings - gaming_fee,
+        gaming_fee,
+        odds_before,
+        odds_after,
     })
 }
 
@@ -779,8 +2293,10 @@ pub fn query_claim_info_by_user(
 
 pub fn query_my_pending_reward(
     deps: Deps<SeiQueryWrapper>,
+    env: Env,
     player: Addr,
 ) -> StdResult<PendingRewardResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let my_game_list = query_my_games_without_limit(deps, player.clone())?;
     let mut winnings = Uint128::zero();
 
@@ -788,46 +2304,12 @@ pub fn query_my_pending_reward(
         let round_id = game.round_id;
         let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
 
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            winnings += game.amount;
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
+        let round = match round {
+            Some(round) if !is_claim_expired(&config, &round, &env) => round,
+            _ => continue,
+        };
 
-            /* Count it up */
-            winnings += round_winnings;
-        }
+        winnings += compute_game_winnings(&round, &game).0;
     }
 
     Ok(PendingRewardResponse {
@@ -837,8 +2319,10 @@ pub fn query_my_pending_reward(
 
 pub fn query_my_pending_reward_rounds(
     deps: Deps<SeiQueryWrapper>,
+    env: Env,
     player: Addr,
 ) -> StdResult<PendingRewardRoundsResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let my_game_list = query_my_games_without_limit(deps, player.clone())?;
     let mut winnings = Uint128::zero();
     let mut winnings_per_round: Vec<(Uint128, Uint128)> = vec![];
@@ -847,49 +2331,15 @@ pub fn query_my_pending_reward_rounds(
         let round_id = game.round_id;
         let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
 
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            winnings += game.amount;
-            winnings_per_round.push((round_id, game.amount));
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
+        let round = match round {
+            Some(round) if !is_claim_expired(&config, &round, &env) => round,
+            _ => continue,
+        };
 
-            /* Count it up */
-            winnings += round_winnings;
-            if round_winnings != Uint128::zero() {
-                winnings_per_round.push((round_id, round_winnings))
-            }
+        let (round_winnings, _) = compute_game_winnings(&round, &game);
+        winnings += round_winnings;
+        if !round_winnings.is_zero() {
+            winnings_per_round.push((round_id, round_winnings));
         }
     }
 
@@ -901,154 +2351,951 @@ pub fn query_my_pending_reward_rounds(
 
 pub fn query_my_pending_reward_round(
     deps: Deps<SeiQueryWrapper>,
+    env: Env,
     round_id: Uint128,
     player: Addr,
 ) -> StdResult<PendingRewardResponse> {
+    let config = CONFIG.load(deps.storage)?;
     let mut winnings = Uint128::zero();
     let mut my_game_list: Vec<BetInfo> = Vec::new();
 
     let bet_info_key = bet_info_key(round_id.u128(), &player);
     let game = bet_info_storage().may_load(deps.storage, bet_info_key)?;
-    match game {
-        Some(_game) => my_game_list.push(_game),
-        None => {}
+    if let Some(game) = game {
+        my_game_list.push(game);
     }
 
     for game in my_game_list {
         let round_id = game.round_id;
         let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
 
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
+        let round = match round {
+            Some(round) if !is_claim_expired(&config, &round, &env) => round,
+            _ => continue,
+        };
 
-        let pool_shares = round.bear_amount + round.bull_amount;
+        winnings += compute_game_winnings(&round, &game).0;
+    }
 
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            winnings += game.amount;
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
+    Ok(PendingRewardResponse {
+        pending_reward: winnings,
+    })
+}
+
+pub fn query_my_games_without_limit(
+    deps: Deps<SeiQueryWrapper>,
+    player: Addr,
+) -> StdResult<MyGameResponse> {
+    let my_game_list = bet_info_storage()
+        .idx
+        .player
+        .prefix(player.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(MyGameResponse { my_game_list })
+}
+
+pub fn query_leaderboard(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    metric: LeaderboardMetric,
+    period: LeaderboardPeriod,
+    limit: Option<u32>,
+    at: Option<Timestamp>,
+) -> StdResult<LeaderboardResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let window = leaderboard_window(&period, at.unwrap_or(env.block.time));
+
+    /* Each index is sorted by score within a window, so this reads `limit` entries */
+    let storage = leaderboard_storage();
+    let scores = match metric {
+        LeaderboardMetric::Volume => storage
+            .idx
+            .volume
+            .sub_prefix(window.clone())
+            .range(deps.storage, None, None, Order::Descending)
+            .take(limit)
+            .map(|res| res.map(|(_, score)| (score.player, score.volume.to_string())))
+            .collect::<StdResult<Vec<_>>>()?,
+        LeaderboardMetric::NetPnl => storage
+            .idx
+            .net_pnl
+            .sub_prefix(window.clone())
+            .range(deps.storage, None, None, Order::Descending)
+            .take(limit)
+            .map(|res| {
+                res.map(|(_, score)| {
+                    (
+                        score.player.clone(),
+                        leaderboard_net_pnl(&score).to_string(),
+                    )
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?,
+        LeaderboardMetric::WinStreak => storage
+            .idx
+            .win_streak
+            .sub_prefix(window.clone())
+            .range(deps.storage, None, None, Order::Descending)
+            .take(limit)
+            .map(|res| res.map(|(_, score)| (score.player, score.best_win_streak.to_string())))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    let entries = scores
+        .into_iter()
+        .enumerate()
+        .map(|(i, (player, value))| LeaderboardEntry {
+            rank: i as u32 + 1,
+            player,
+            value,
+        })
+        .collect();
+
+    Ok(LeaderboardResponse {
+        metric,
+        period,
+        window,
+        entries,
+    })
+}
+
+fn query_global_stats(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    days: Option<u32>,
+) -> StdResult<GlobalStatsResponse> {
+    let days = days.unwrap_or(DEFAULT_STATS_DAYS).min(MAX_STATS_DAYS) as u64;
+    let today = env.block.time.seconds() / DAY;
+    let first_day = (today + 1).saturating_sub(days);
+
+    let days = DAILY_STATS
+        .range(
+            deps.storage,
+            Some(Bound::inclusive(first_day)),
+            None,
+            Order::Ascending,
+        )
+        .map(|res| res.map(|(day, stats)| DailyStats { day, stats }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(GlobalStatsResponse {
+        totals: GLOBAL_STATS.may_load(deps.storage)?.unwrap_or_default(),
+        days,
+    })
+}
+
+fn query_seasons(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SeasonsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let seasons = SEASONS
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|res| res.map(|(_, season)| season))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SeasonsResponse { seasons })
+}
+
+fn query_player_stats(deps: Deps<SeiQueryWrapper>, player: Addr) -> StdResult<PlayerStatsResponse> {
+    let stats = PLAYER_STATS
+        .may_load(deps.storage, player.clone())?
+        .unwrap_or_default();
+    let net_pnl = if stats.total_won >= stats.settled_wagered {
+        (stats.total_won - stats.settled_wagered).to_string()
+    } else {
+        format!("-{}", stats.settled_wagered - stats.total_won)
+    };
+    let decided = stats.rounds_won + stats.rounds_lost;
+    let win_rate = if decided == 0 {
+        None
+    } else {
+        Some(Decimal::from_ratio(stats.rounds_won, decided))
+    };
+
+    Ok(PlayerStatsResponse {
+        player,
+        stats,
+        net_pnl,
+        win_rate,
+    })
+}
+
+fn query_total_spent(deps: Deps<SeiQueryWrapper>, player: Addr) -> StdResult<TotalSpentResponse> {
+    let total = TOTALS_SPENT.may_load(deps.storage, player)?;
+
+    Ok(TotalSpentResponse {
+        total_spent: total.unwrap_or(Uint128::zero()),
+    })
+}
+
+pub fn query_get_admins(deps: Deps<SeiQueryWrapper>) -> StdResult<AdminsResponse> {
+    let admins = query_owners(deps.storage)?;
+
+    Ok(AdminsResponse { admins })
+}
+
+pub fn query_pending_config_changes(
+    deps: Deps<SeiQueryWrapper>,
+) -> StdResult<PendingConfigChangesResponse> {
+    let changes = PENDING_CONFIG_CHANGES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PendingConfigChangesResponse { changes })
+}
+
+pub fn query_ownership_proposal(
+    deps: Deps<SeiQueryWrapper>,
+    candidate: Option<Addr>,
+) -> StdResult<OwnershipProposalResponse> {
+    let proposals = match candidate {
+        Some(candidate) => OWNERSHIP_PROPOSALS
+            .may_load(deps.storage, candidate)?
+            .into_iter()
+            .collect(),
+        None => OWNERSHIP_PROPOSALS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|res| res.map(|item| item.1))
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+
+    Ok(OwnershipProposalResponse { proposals })
+}
+
+pub fn query_roles(deps: Deps<SeiQueryWrapper>, address: Addr) -> StdResult<RolesResponse> {
+    let roles = ROLES
+        .may_load(deps.storage, address.clone())?
+        .unwrap_or_default();
+
+    Ok(RolesResponse { address, roles })
+}
+
+pub fn query_claim_expiry(
+    deps: Deps<SeiQueryWrapper>,
+    env: Env,
+    round_id: Uint128,
+) -> StdResult<ClaimExpiryResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
+
+    let expires_at = round.and_then(|round| claim_expires_at(&config, &round));
+
+    Ok(ClaimExpiryResponse {
+        round_id,
+        expires_at,
+        expired: matches!(expires_at, Some(expires_at) if env.block.time >= expires_at),
+    })
+}
+
+fn assert_is_current_round(deps: Deps<SeiQueryWrapper>, round_id: Uint128) -> StdResult<NextRound> {
+    let open_round = NEXT_ROUND.load(deps.storage)?;
+
+    if round_id != open_round.id {
+        return Err(StdError::generic_err(format!(
+            "Tried to open at round {} but it's currently round {}",
+            round_id, open_round.id
+        )));
+    }
+
+    Ok(open_round)
+}
+
+fn compute_gaming_fee(config: &Config, gross: Uint128) -> StdResult<Uint128> {
+    config
+        .gaming_fee
+        .checked_multiply_ratio(gross, FEE_PRECISION * 100)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+fn compute_round_open(
+    config: &Config,
+    env: &Env,
+    round: &NextRound,
+    open_price: Decimal,
+) -> LiveRound {
+    LiveRound {
+        id: round.id,
+        bid_time: round.bid_time,
+        open_time: env.block.time,
+        close_time: env
+            .block
+            .time
+            .plus_seconds(config.next_round_seconds.u128() as u64),
+        open_price,
+        bull_amount: round.bull_amount,
+        bear_amount: round.bear_amount,
+    }
+}
+
+fn get_current_price(deps: Deps<SeiQueryWrapper>, env: &Env) -> Result<PriceRead, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    read_price(deps, env, &config)
+}
+
+/// The price of the bet token, missing when a median didn't get a quorum
+struct PriceRead {
+    price: Option<Decimal>,
+    /* What each source of a median returned */
+    source_prices: Vec<SourcePrice>,
+}
+
+/// A single source's price and when it was published, if it says
+struct SourceReading {
+    price: Decimal,
+    published: Option<Timestamp>,
+}
+
+fn price_source(config: &Config) -> PriceSource {
+    config
+        .price_source
+        .clone()
+        .unwrap_or(PriceSource::SeiOracle {})
+}
+
+/// Reads the price of the bet token from the configured source
+fn read_price(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+) -> Result<PriceRead, ContractError> {
+    let (sources, quorum, max_age_seconds) = match price_source(config) {
+        PriceSource::Median {
+            sources,
+            quorum,
+            max_age_seconds,
+        } => (sources, quorum, max_age_seconds),
+        /* Feeders short of their quorum, or a pair not observed for a whole
+        TWAP window yet, void the round like a median would */
+        source @ (PriceSource::Feeders { .. } | PriceSource::AmmPair { .. }) => {
+            let price = match read_source(deps, env, config, &source) {
+                Ok(reading) => Some(reading.price),
+                Err(ContractError::QuorumNotMet {} | ContractError::NoAmmObservation { .. }) => {
+                    None
                 }
+                Err(err) => return Err(err),
             };
+            return Ok(PriceRead {
+                price,
+                source_prices: vec![],
+            });
+        }
+        source => {
+            return Ok(PriceRead {
+                price: Some(read_source(deps, env, config, &source)?.price),
+                source_prices: vec![],
+            })
+        }
+    };
+
+    /* A source failing or lagging only costs its vote */
+    let source_prices = sources
+        .into_iter()
+        .map(|source| {
+            let price = read_source(deps, env, config, &source)
+                .ok()
+                .filter(|reading| match (reading.published, max_age_seconds) {
+                    (Some(published), Some(max_age)) => {
+                        published.plus_seconds(max_age) >= env.block.time
+                    }
+                    _ => true,
+                })
+                .map(|reading| reading.price);
+            SourcePrice { source, price }
+        })
+        .collect::<Vec<_>>();
+
+    let mut prices = source_prices
+        .iter()
+        .filter_map(|source_price| source_price.price)
+        .collect::<Vec<_>>();
+    let price = if prices.len() >= quorum as usize {
+        Some(median(&mut prices))
+    } else {
+        None
+    };
+
+    Ok(PriceRead {
+        price,
+        source_prices,
+    })
+}
+
+fn read_source(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+    source: &PriceSource,
+) -> Result<SourceReading, ContractError> {
+    let price = match source {
+        PriceSource::Feeders {
+            quorum,
+            max_age_seconds,
+            tolerance,
+        } => {
+            return read_feeder_prices(
+                deps,
+                env,
+                &config.bet_token_denom,
+                *quorum,
+                *max_age_seconds,
+                *tolerance,
+            )
+        }
+        PriceSource::Pyth {
+            contract,
+            feed_id,
+            max_confidence,
+            max_age_seconds,
+        } => {
+            return read_pyth_price(
+                deps,
+                env,
+                contract,
+                feed_id,
+                *max_confidence,
+                *max_age_seconds,
+            )
+        }
+        PriceSource::AmmPair {
+            contract,
+            pool_type,
+            min_liquidity,
+            twap_window_seconds,
+        } => {
+            return read_amm_price(
+                deps,
+                env,
+                &config.bet_token_denom,
+                contract,
+                pool_type,
+                *min_liquidity,
+                *twap_window_seconds,
+            )
+        }
+        PriceSource::SeiOracle {} => query_oracle_price(deps, &config.bet_token_denom)?,
+        PriceSource::SeiOracleTwap { lookback_seconds } => {
+            query_oracle_twap(deps, &config.bet_token_denom, *lookback_seconds)?
+        }
+        PriceSource::Median { .. } => {
+            return Err(ContractError::InvalidPriceSource {
+                reason: "medians can't be nested".to_string(),
+            })
+        }
+    };
+    /* The oracle module drops rates that stopped being voted on */
+    Ok(SourceReading {
+        price,
+        published: None,
+    })
+}
+
+/**
+ * The median of the fresh prices the feeders agree on. Pushes further than
+ * the tolerance from the median of all fresh pushes are left out, and at
+ * least `quorum` have to remain.
+ */
+fn read_feeder_prices(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    market: &str,
+    quorum: u32,
+    max_age_seconds: u64,
+    tolerance: Option<Decimal>,
+) -> Result<SourceReading, ContractError> {
+    let pushes = FEEDER_PRICES
+        .prefix(market)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(_, push)| push))
+        .filter(|res| {
+            !matches!(res, Ok(push) if push.timestamp.plus_seconds(max_age_seconds) < env.block.time)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    if pushes.is_empty() {
+        return Err(ContractError::QuorumNotMet {});
+    }
+
+    let mut prices = pushes.iter().map(|push| push.price).collect::<Vec<_>>();
+    let reference = median(&mut prices);
+    let agreeing = pushes
+        .iter()
+        .filter(|push| match tolerance {
+            Some(tolerance) => {
+                let deviation = if push.price > reference {
+                    push.price - reference
+                } else {
+                    reference - push.price
+                };
+                deviation <= reference * tolerance
+            }
+            None => true,
+        })
+        .collect::<Vec<_>>();
+    if agreeing.len() < quorum as usize {
+        return Err(ContractError::QuorumNotMet {});
+    }
+
+    let mut prices = agreeing.iter().map(|push| push.price).collect::<Vec<_>>();
+    Ok(SourceReading {
+        price: median(&mut prices),
+        published: agreeing.iter().map(|push| push.timestamp).min(),
+    })
+}
+
+/// A Pyth feed's price, refused when it is stale or too uncertain
+fn read_pyth_price(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    contract: &Addr,
+    feed_id: &str,
+    max_confidence: Decimal,
+    max_age_seconds: u64,
+) -> Result<SourceReading, ContractError> {
+    let res: pyth::PriceFeedResponse = deps.querier.query_wasm_smart(
+        contract,
+        &pyth::QueryMsg::PriceFeed {
+            id: feed_id.to_string(),
+        },
+    )?;
+    let feed_price = res.price_feed.price;
+
+    let published = Timestamp::from_seconds(feed_price.publish_time.max(0) as u64);
+    if published.plus_seconds(max_age_seconds) < env.block.time {
+        return Err(ContractError::StalePrice { published });
+    }
+
+    let price = pyth_decimal(feed_price.price, feed_price.expo)?;
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
+    let confidence = pyth_decimal(feed_price.conf, feed_price.expo)?;
+    if confidence > price * max_confidence {
+        return Err(ContractError::PriceTooUncertain {
+            price,
+            confidence,
+            max_confidence,
+        });
+    }
+
+    Ok(SourceReading {
+        price,
+        published: Some(published),
+    })
+}
+
+/// `value * 10^expo` as a decimal
+fn pyth_decimal(value: Uint64, expo: i32) -> Result<Decimal, ContractError> {
+    let value = Uint128::from(value);
+    let decimal = if expo < 0 {
+        Decimal::from_atomics(value, expo.unsigned_abs()).ok()
+    } else {
+        Uint128::new(10)
+            .checked_pow(expo as u32)
+            .and_then(|scale| value.checked_mul(scale))
+            .ok()
+            .and_then(|atomics| Decimal::from_atomics(atomics, 0).ok())
+    };
+    decimal.ok_or(ContractError::InvalidPrice {})
+}
+
+/**
+ * An AMM pair's price of `denom` in its other asset. With a TWAP window it is
+ * averaged since the newest observation of the pair's accumulator at least a
+ * window old, and can't be read until there is one.
+ */
+fn read_amm_price(
+    deps: Deps<SeiQueryWrapper>,
+    env: &Env,
+    denom: &str,
+    contract: &Addr,
+    pool_type: &PoolType,
+    min_liquidity: Uint128,
+    twap_window_seconds: Option<u64>,
+) -> Result<SourceReading, ContractError> {
+    let pool: amm::PoolResponse = deps
+        .querier
+        .query_wasm_smart(contract, &amm::QueryMsg::Pool {})?;
+    let (base, quote) = match pool.assets.as_slice() {
+        [first, second] if first.info.is(denom) => (first.clone(), second.clone()),
+        [first, second] if second.info.is(denom) => (second.clone(), first.clone()),
+        _ => {
+            return Err(ContractError::InvalidPriceSource {
+                reason: format!("the pair doesn't trade {}", denom),
+            })
+        }
+    };
+    /* A shallow pool is cheap to move */
+    if quote.amount < min_liquidity {
+        return Err(ContractError::LowLiquidity {
+            reserve: quote.amount,
+            min_liquidity,
+        });
+    }
+    if base.amount.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
 
-            /* Count it up */
-            winnings += round_winnings;
+    let price = match twap_window_seconds {
+        Some(window) => {
+            let observation = amm_twap_start(deps.storage, env, contract, denom, window)?.ok_or(
+                ContractError::NoAmmObservation {
+                    window_seconds: window,
+                },
+            )?;
+            let cumulative_price = query_cumulative_price(deps, contract, denom)?;
+            let elapsed = env.block.time.seconds() - observation.time.seconds();
+            Decimal::checked_from_ratio(
+                cumulative_price.wrapping_sub(observation.cumulative_price),
+                Uint128::from(elapsed) * Uint128::new(amm::TWAP_PRECISION),
+            )
+            .map_err(|_| ContractError::InvalidPrice {})?
         }
+        None => match pool_type {
+            PoolType::Xyk => Decimal::checked_from_ratio(quote.amount, base.amount)
+                .map_err(|_| ContractError::InvalidPrice {})?,
+            /* The reserves' ratio isn't the price of a stable pool, a swap too
+            small to move it tells */
+            PoolType::Stable => {
+                let offer = (base.amount / Uint128::new(STABLE_PRICE_PROBE)).max(Uint128::one());
+                let res: amm::SimulationResponse = deps.querier.query_wasm_smart(
+                    contract,
+                    &amm::QueryMsg::Simulation {
+                        offer_asset: amm::Asset {
+                            info: base.info,
+                            amount: offer,
+                        },
+                        ask_asset_info: Some(quote.info),
+                    },
+                )?;
+                Decimal::checked_from_ratio(res.return_amount + res.commission_amount, offer)
+                    .map_err(|_| ContractError::InvalidPrice {})?
+            }
+        },
+    };
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
     }
 
-    Ok(PendingRewardResponse {
-        pending_reward: winnings,
+    Ok(SourceReading {
+        price,
+        published: None,
     })
 }
 
-pub fn query_my_games_without_limit(
+fn query_cumulative_price(
     deps: Deps<SeiQueryWrapper>,
-    player: Addr,
-) -> StdResult<MyGameResponse> {
-    let my_game_list = bet_info_storage()
-        .idx
-        .player
-        .prefix(player.clone())
-        .range(deps.storage, None, None, Order::Ascending)
-        .map(|res| res.map(|item| item.1))
-        .collect::<StdResult<Vec<_>>>()?;
-    Ok(MyGameResponse { my_game_list })
+    contract: &Addr,
+    denom: &str,
+) -> Result<Uint128, ContractError> {
+    let res: amm::CumulativePricesResponse = deps
+        .querier
+        .query_wasm_smart(contract, &amm::QueryMsg::CumulativePrices {})?;
+    res.cumulative_prices
+        .into_iter()
+        .find(|(asset, _, _)| asset.is(denom))
+        .map(|(_, _, cumulative_price)| cumulative_price)
+        .ok_or_else(|| ContractError::InvalidPriceSource {
+            reason: format!("the pair doesn't accumulate the price of {}", denom),
+        })
 }
 
-pub fn query_total_spent(
-    deps: Deps<SeiQueryWrapper>,
-    player: Addr,
-) -> StdResult<TotalSpentResponse> {
-    let total = TOTALS_SPENT.may_load(deps.storage, player)?;
-
-    Ok(TotalSpentResponse {
-        total_spent: total.unwrap_or(Uint128::zero()),
-    })
+/// The newest observation of the pair's accumulator at least `window` old
+fn amm_twap_start(
+    storage: &dyn Storage,
+    env: &Env,
+    contract: &Addr,
+    denom: &str,
+    window: u64,
+) -> StdResult<Option<AmmObservation>> {
+    let start = match env.block.time.seconds().checked_sub(window) {
+        Some(start) => start,
+        None => return Ok(None),
+    };
+    AMM_OBSERVATIONS
+        .prefix((contract, denom))
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive(start)),
+            Order::Descending,
+        )
+        .next()
+        .transpose()
+        .map(|observation| observation.map(|(_, observation)| observation))
 }
 
-pub fn query_get_admins(deps: Deps<SeiQueryWrapper>) -> StdResult<AdminsResponse> {
-    let admins = ADMINS.load(deps.storage)?;
+/**
+ * Observes the AMM price accumulators of the configured sources as a round
+ * closes. The observations older than the one the TWAPs start from are no
+ * longer needed and dropped.
+ */
+fn record_amm_observations(
+    deps: DepsMut<SeiQueryWrapper>,
+    env: &Env,
+    config: &Config,
+) -> Result<(), ContractError> {
+    let sources = match price_source(config) {
+        PriceSource::Median { sources, .. } => sources,
+        source => vec![source],
+    };
+    let denom = config.bet_token_denom.as_str();
+    let now = env.block.time.seconds();
+    for source in sources {
+        if let PriceSource::AmmPair {
+            contract,
+            twap_window_seconds: Some(window),
+            ..
+        } = source
+        {
+            /* A failing pair is reported when its price is read */
+            if let Ok(cumulative_price) = query_cumulative_price(deps.as_ref(), &contract, denom) {
+                AMM_OBSERVATIONS.save(
+                    deps.storage,
+                    (&contract, denom, now),
+                    &AmmObservation {
+                        time: env.block.time,
+                        cumulative_price,
+                    },
+                )?;
+            }
 
-    Ok(AdminsResponse { admins })
+            let stale = match now.checked_sub(window) {
+                Some(start) => AMM_OBSERVATIONS
+                    .prefix((&contract, denom))
+                    .keys(
+                        deps.storage,
+                        None,
+                        Some(Bound::inclusive(start)),
+                        Order::Descending,
+                    )
+                    .skip(1)
+                    .collect::<StdResult<Vec<_>>>()?,
+                None => vec![],
+            };
+            for time in stale {
+                AMM_OBSERVATIONS.remove(deps.storage, (&contract, denom, time));
+            }
+        }
+    }
+    Ok(())
 }
 
-fn assert_is_current_round(deps: Deps<SeiQueryWrapper>, round_id: Uint128) -> StdResult<NextRound> {
-    let open_round = NEXT_ROUND.load(deps.storage)?;
-
-    if round_id != open_round.id {
-        return Err(StdError::generic_err(format!(
-            "Tried to open at round {} but it's currently round {}",
-            round_id, open_round.id
-        )));
+/// The sources a config has to be able to read as it is set. Feeders and
+/// Pyth feeds have no price until one is pushed, and may be stale meanwhile,
+/// so they are only checked for their shape. AMM pairs are read on their spot
+/// price, as their TWAP needs observations made as rounds close.
+fn checked_price_sources(source: PriceSource) -> Vec<PriceSource> {
+    match source {
+        PriceSource::Feeders { .. } | PriceSource::Pyth { .. } => vec![],
+        PriceSource::AmmPair {
+            contract,
+            pool_type,
+            min_liquidity,
+            ..
+        } => vec![PriceSource::AmmPair {
+            contract,
+            pool_type,
+            min_liquidity,
+            twap_window_seconds: None,
+        }],
+        PriceSource::Median { sources, .. } => sources
+            .into_iter()
+            .flat_map(checked_price_sources)
+            .collect(),
+        source => vec![source],
     }
-
-    Ok(open_round)
 }
 
-fn compute_gaming_fee(deps: Deps<SeiQueryWrapper>, gross: Uint128) -> StdResult<Uint128> {
-    let staker_fee = CONFIG.load(deps.storage)?.gaming_fee;
+fn execute_push_price(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    market: String,
+    price: Decimal,
+    timestamp: Timestamp,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::OracleFeeder)?;
+    nonpayable(&info)?;
+    let config = CONFIG.load(deps.storage)?;
+    if market != config.bet_token_denom {
+        return Err(ContractError::UnknownMarket { market });
+    }
+    if price.is_zero() {
+        return Err(ContractError::InvalidPrice {});
+    }
 
-    staker_fee
-        .checked_multiply_ratio(gross, FEE_PRECISION * 100)
-        .map_err(|e| StdError::generic_err(e.to_string()))
+    /* Pushes can't be replayed or come from the future */
+    let key = (market.as_str(), info.sender.clone());
+    let last = FEEDER_PRICES.may_load(deps.storage, key.clone())?;
+    if timestamp > env.block.time || matches!(last, Some(last) if timestamp <= last.timestamp) {
+        return Err(ContractError::InvalidPriceTimestamp { timestamp });
+    }
+    FEEDER_PRICES.save(
+        deps.storage,
+        key,
+        &FeederPrice {
+            feeder: info.sender.clone(),
+            price,
+            timestamp,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-push-price")
+        .add_attribute("market", market)
+        .add_attribute("feeder", info.sender)
+        .add_attribute("price", price.to_string())
+        .add_attribute("timestamp", timestamp.to_string()))
 }
 
-fn compute_round_open(
+fn query_feeder_prices(
     deps: Deps<SeiQueryWrapper>,
-    env: Env,
-    round: &NextRound,
-) -> Result<LiveRound, ContractError> {
-    /* TODO */
-    let open_price = get_current_price(deps)?;
-    let config = CONFIG.load(deps.storage)?;
+    market: Option<String>,
+) -> StdResult<FeederPricesResponse> {
+    let market = match market {
+        Some(market) => market,
+        None => CONFIG.load(deps.storage)?.bet_token_denom,
+    };
+    let prices = FEEDER_PRICES
+        .prefix(&market)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| res.map(|(_, push)| push))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(FeederPricesResponse { market, prices })
+}
 
-    Ok(LiveRound {
-        id: round.id,
-        bid_time: round.bid_time,
-        open_time: env.block.time,
-        close_time: env
-            .block
-            .time
-            .plus_seconds(config.next_round_seconds.u128() as u64),
-        open_price,
-        bull_amount: round.bull_amount,
-        bear_amount: round.bear_amount,
-    })
+/// Median of a non empty list of prices
+fn median(prices: &mut [Decimal]) -> Decimal {
+    prices.sort();
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        prices[middle]
+    } else {
+        (prices[middle - 1] + prices[middle]) * Decimal::percent(50)
+    }
 }
 
-fn get_current_price(deps: Deps<SeiQueryWrapper>) -> Result<Decimal, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
+/// Checks the shape of a price source, reading it is left to the caller
+fn validate_price_source(
+    api: &dyn Api,
+    source: &PriceSource,
+    nested: bool,
+) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidPriceSource {
+        reason: reason.to_string(),
+    };
+    match source {
+        PriceSource::SeiOracle {} => Ok(()),
+        PriceSource::SeiOracleTwap { lookback_seconds } => {
+            if *lookback_seconds == 0 {
+                return Err(ContractError::ZeroDuration {
+                    field: "lookback_seconds".to_string(),
+                });
+            }
+            Ok(())
+        }
+        PriceSource::Feeders {
+            quorum,
+            max_age_seconds,
+            tolerance,
+        } => {
+            if *quorum == 0 {
+                return Err(invalid("feeders need a quorum of at least 1"));
+            }
+            if *max_age_seconds == 0 {
+                return Err(ContractError::ZeroDuration {
+                    field: "max_age_seconds".to_string(),
+                });
+            }
+            if matches!(tolerance, Some(tolerance) if *tolerance >= Decimal::one()) {
+                return Err(invalid("tolerance has to be less than 1"));
+            }
+            Ok(())
+        }
+        PriceSource::Pyth {
+            contract,
+            feed_id,
+            max_confidence,
+            max_age_seconds,
+        } => {
+            api.addr_validate(contract.as_str())?;
+            let hex = feed_id.strip_prefix("0x").unwrap_or(feed_id);
+            if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid("a Pyth feed id is 32 bytes in hex"));
+            }
+            if max_confidence.is_zero() {
+                return Err(invalid("max_confidence can't be zero"));
+            }
+            if *max_age_seconds == 0 {
+                return Err(ContractError::ZeroDuration {
+                    field: "max_age_seconds".to_string(),
+                });
+            }
+            Ok(())
+        }
+        PriceSource::AmmPair {
+            contract,
+            min_liquidity,
+            twap_window_seconds,
+            ..
+        } => {
+            api.addr_validate(contract.as_str())?;
+            if min_liquidity.is_zero() {
+                return Err(invalid("min_liquidity can't be zero"));
+            }
+            if *twap_window_seconds == Some(0) {
+                return Err(ContractError::ZeroDuration {
+                    field: "twap_window_seconds".to_string(),
+                });
+            }
+            Ok(())
+        }
+        PriceSource::Median { .. } if nested => Err(invalid("medians can't be nested")),
+        PriceSource::Median {
+            sources,
+            quorum,
+            max_age_seconds,
+        } => {
+            if sources.is_empty() || sources.len() > MAX_PRICE_SOURCES {
+                return Err(invalid(&format!(
+                    "a median needs between 1 and {} sources",
+                    MAX_PRICE_SOURCES
+                )));
+            }
+            if *quorum == 0 || *quorum as usize > sources.len() {
+                return Err(invalid(
+                    "quorum has to be between 1 and the number of sources",
+                ));
+            }
+            if *max_age_seconds == Some(0) {
+                return Err(ContractError::ZeroDuration {
+                    field: "max_age_seconds".to_string(),
+                });
+            }
+            sources
+                .iter()
+                .try_for_each(|source| validate_price_source(api, source, true))
+        }
+    }
+}
+
+fn query_oracle_price(deps: Deps<SeiQueryWrapper>, denom: &str) -> Result<Decimal, ContractError> {
     let querier = SeiQuerier::new(&deps.querier);
     let res: ExchangeRatesResponse = querier.query_exchange_rates()?;
 
     let exchange_rate = res
         .denom_oracle_exchange_rate_pairs
         .iter()
-        .find(|rate| config.bet_token_denom == rate.denom);
+        .find(|rate| denom == rate.denom);
 
     if exchange_rate.is_none() {
         return Err(ContractError::PriceNotFoundInOracle {});
@@ -1057,12 +3304,22 @@ fn get_current_price(deps: Deps<SeiQueryWrapper>) -> Result<Decimal, ContractErr
     Ok(exchange_rate.unwrap().oracle_exchange_rate.exchange_rate)
 }
 
-fn compute_round_close(
+fn query_oracle_twap(
     deps: Deps<SeiQueryWrapper>,
-    round: &LiveRound,
-) -> Result<FinishedRound, ContractError> {
-    let close_price = get_current_price(deps)?;
+    denom: &str,
+    lookback_seconds: u64,
+) -> Result<Decimal, ContractError> {
+    let querier = SeiQuerier::new(&deps.querier);
+    let res: OracleTwapsResponse = querier.query_oracle_twaps(lookback_seconds)?;
 
+    res.oracle_twaps
+        .into_iter()
+        .find(|twap| denom == twap.denom)
+        .map(|twap| twap.twap)
+        .ok_or(ContractError::PriceNotFoundInOracle {})
+}
+
+fn compute_round_close(config: &Config, round: &LiveRound, close_price: Decimal) -> FinishedRound {
     let winner = match close_price.cmp(&round.open_price) {
         std::cmp::Ordering::Greater =>
         /* Bulls win */
@@ -1081,7 +3338,7 @@ fn compute_round_close(
         }
     };
 
-    Ok(FinishedRound {
+    FinishedRound {
         id: round.id,
         bid_time: round.bid_time,
         open_time: round.open_time,
@@ -1091,90 +3348,626 @@ fn compute_round_close(
         bull_amount: round.bull_amount,
         winner,
         close_price,
-    })
+        pricing: Some(PricingMethod {
+            source: price_source(config),
+            twap_samples: config.price_twap_samples,
+        }),
+        voided: false,
+    }
+}
+
+/// A round that couldn't be priced
+fn void_round(round: FinishedRound) -> FinishedRound {
+    FinishedRound {
+        close_price: Decimal::zero(),
+        winner: None,
+        voided: true,
+        ..round
+    }
+}
+
+/// Saves a finished round, queues it to be scored and returns its settlement
+/// event
+fn finish_round(
+    storage: &mut dyn Storage,
+    env: &Env,
+    round: FinishedRound,
+    source_prices: Vec<SourcePrice>,
+) -> StdResult<Event> {
+    ROUNDS.save(storage, round.id.u128(), &round)?;
+    UNSCORED_ROUNDS.save(storage, round.id.u128(), &None)?;
+    update_global_stats(storage, env.block.time, |stats| {
+        stats.total_rounds += 1;
+        match round.winner {
+            _ if round.voided => stats.voided += 1,
+            Some(Direction::Bull) => stats.bull_wins += 1,
+            Some(Direction::Bear) => stats.bear_wins += 1,
+            None => stats.draws += 1,
+        }
+    })?;
+
+    Ok(RoundSettled {
+        round_id: round.id,
+        open_price: round.open_price,
+        close_price: round.close_price,
+        winner: round.winner,
+        bull_amount: round.bull_amount,
+        bear_amount: round.bear_amount,
+        pricing: round.pricing,
+        source_prices,
+        voided: round.voided,
+    }
+    .to_event())
+}
+
+/// Adds the price read in this block to the history, `mark` noting what it
+/// was read for
+fn record_price_sample(
+    storage: &mut dyn Storage,
+    env: &Env,
+    price: Decimal,
+    mark: impl FnOnce(&mut PriceSample),
+) -> StdResult<()> {
+    let time = env.block.time;
+    let mut sample = PRICE_HISTORY
+        .may_load(storage, time.seconds())?
+        .unwrap_or(PriceSample {
+            time,
+            price,
+            opened_round: None,
+            closed_round: None,
+        });
+    mark(&mut sample);
+    PRICE_HISTORY.save(storage, time.seconds(), &sample)
 }
 
-fn assert_not_halted(deps: Deps<SeiQueryWrapper>) -> StdResult<bool> {
-    let is_halted = IS_HALTED.load(deps.storage)?;
-    if is_halted {
-        return Err(StdError::generic_err("Contract is halted"));
+/**
+ * The price a round opens or closes at: the current price, or its time
+ * weighted average since `since` when configured. Each recorded sample stands
+ * for the time since the one before it, the oldest for the time since
+ * `since`, so the current price counts for as long as it has been since the
+ * last checkpoint. Only the last samples are read.
+ */
+fn compute_settlement_price(
+    storage: &dyn Storage,
+    env: &Env,
+    config: &Config,
+    since: Timestamp,
+    price: Decimal,
+) -> StdResult<Decimal> {
+    let samples = match config.price_twap_samples {
+        Some(samples) => samples as usize,
+        None => return Ok(price),
+    };
+    let now = env.block.time.seconds();
+    if now <= since.seconds() {
+        return Ok(price);
     }
-    Ok(true)
+
+    let mut history = PRICE_HISTORY
+        .range(
+            storage,
+            Some(Bound::exclusive(since.seconds())),
+            Some(Bound::inclusive(now)),
+            Order::Descending,
+        )
+        .take(samples)
+        .map(|res| res.map(|(_, sample)| sample))
+        .collect::<StdResult<Vec<_>>>()?;
+    history.reverse();
+
+    let window = now - since.seconds();
+    let (twap, last) = history.iter().fold(
+        (Decimal::zero(), since.seconds()),
+        |(twap, last), sample| {
+            let held = sample.time.seconds() - last;
+            (
+                twap + sample.price * Decimal::from_ratio(held, window),
+                sample.time.seconds(),
+            )
+        },
+    );
+    /* Up to now if the current price wasn't recorded */
+    Ok(twap + price * Decimal::from_ratio(now - last, window))
 }
 
-fn execute_update_halt(
+fn execute_checkpoint_price(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
-    is_halted: bool,
+    env: Env,
+    price_updates: Option<Vec<Binary>>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    IS_HALTED.save(deps.storage, &is_halted)?;
-    Ok(Response::new().add_event(Event::new("fuzio_beta").add_attribute("halt_games", "true")))
+    assert_has_role(deps.as_ref(), &info.sender, Role::OracleFeeder)?;
+    match price_updates {
+        Some(data) => execute_update_pyth_prices(deps.as_ref(), info, data, REPLY_CHECKPOINT_PRICE),
+        None => checkpoint_price(deps, env),
+    }
 }
 
-fn assert_is_admin(deps: Deps<SeiQueryWrapper>, info: MessageInfo) -> StdResult<bool> {
-    let admins = ADMINS.load(deps.storage)?;
-    if !admins.contains(&info.sender) {
-        return Err(StdError::generic_err(format!(
-            "Only an admin can execute this function. Sender: {}",
-            info.sender
-        )));
+fn checkpoint_price(deps: DepsMut<SeiQueryWrapper>, env: Env) -> Result<Response, ContractError> {
+    assert_not_paused(deps.as_ref(), Pausable::Rounds)?;
+
+    let price = get_current_price(deps.as_ref(), &env)?
+        .price
+        .ok_or(ContractError::QuorumNotMet {})?;
+    record_price_sample(deps.storage, &env, price, |_| {})?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-checkpoint-price")
+        .add_attribute("price", price.to_string()))
+}
+
+/**
+ * Pushes price updates to the Pyth contract the price is read from, paying
+ * the update fee with the funds sent, then carries on with `reply_id` once
+ * they are in
+ */
+fn execute_update_pyth_prices(
+    deps: Deps<SeiQueryWrapper>,
+    info: MessageInfo,
+    data: Vec<Binary>,
+    reply_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let contract = pyth_contract(&price_source(&config))?;
+    let update = WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(&pyth::ExecuteMsg::UpdatePriceFeeds { data })?,
+        funds: info.funds,
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(update, reply_id))
+        .add_attribute("action", "fuzio-update-pyth-prices")
+        .add_attribute("pyth_contract", contract))
+}
+
+/// The Pyth contract the price source reads, a median having to read one only
+fn pyth_contract(source: &PriceSource) -> Result<Addr, ContractError> {
+    let mut contracts = match source {
+        PriceSource::Pyth { contract, .. } => vec![contract.clone()],
+        PriceSource::Median { sources, .. } => sources
+            .iter()
+            .filter_map(|source| match source {
+                PriceSource::Pyth { contract, .. } => Some(contract.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    contracts.sort();
+    contracts.dedup();
+    match contracts.as_slice() {
+        [contract] => Ok(contract.clone()),
+        _ => Err(ContractError::InvalidPriceSource {
+            reason: "price updates need a single Pyth contract to push them to".to_string(),
+        }),
+    }
+}
+
+fn query_price_history(
+    deps: Deps<SeiQueryWrapper>,
+    start_after: Option<Timestamp>,
+    limit: Option<u32>,
+) -> StdResult<PriceHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+    let samples = PRICE_HISTORY
+        .range(
+            deps.storage,
+            start_after.map(|time| Bound::exclusive(time.seconds())),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|res| res.map(|(_, sample)| sample))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(PriceHistoryResponse { samples })
+}
+
+/// What can be paused on its own
+#[derive(Clone, Copy)]
+enum Pausable {
+    Betting,
+    Rounds,
+    Claims,
+}
+
+fn assert_not_paused(deps: Deps<SeiQueryWrapper>, pausable: Pausable) -> Result<(), ContractError> {
+    let pause_state = PAUSE_STATE.load(deps.storage)?;
+    let (paused, action) = match pausable {
+        Pausable::Betting => (pause_state.betting, "Betting"),
+        Pausable::Rounds => (pause_state.rounds, "Round progression"),
+        Pausable::Claims => (pause_state.claims, "Claiming"),
+    };
+    if paused {
+        return Err(ContractError::Paused {
+            action: action.to_string(),
+        });
     }
+    Ok(())
+}
 
-    Ok(true)
+fn assert_not_emergency(deps: Deps<SeiQueryWrapper>) -> Result<(), ContractError> {
+    if EMERGENCY.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::EmergencyShutdown {});
+    }
+    Ok(())
 }
 
-fn execute_add_admin(
+fn execute_emergency_shutdown(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
-    new_admin: Addr,
+    env: Env,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    deps.api.addr_validate(&new_admin.to_string())?;
-    let mut admins = ADMINS.load(deps.storage)?;
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    assert_not_emergency(deps.as_ref())?;
+
+    /* Neither round will finish, so their bets are given back */
+    let mut voided_rounds = vec![];
+    if let Some(live_round) = LIVE_ROUND.may_load(deps.storage)? {
+        voided_rounds.push(live_round.id);
+        LIVE_ROUND.remove(deps.storage);
+    }
+    if let Some(next_round) = NEXT_ROUND.may_load(deps.storage)? {
+        voided_rounds.push(next_round.id);
+        NEXT_ROUND.remove(deps.storage);
+    }
+
+    let emergency = EmergencyState {
+        triggered_at: env.block.time,
+        triggered_by: info.sender,
+        voided_rounds,
+    };
+    EMERGENCY.save(deps.storage, &emergency)?;
+
+    Ok(Response::new().add_event(
+        Event::new("fuzio_emergency_shutdown")
+            .add_attribute("triggered_by", emergency.triggered_by)
+            .add_attribute(
+                "voided_rounds",
+                emergency
+                    .voided_rounds
+                    .iter()
+                    .map(|round_id| round_id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+    ))
+}
 
-    admins.push(new_admin.clone());
+fn execute_emergency_withdraw(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let emergency = EMERGENCY
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotInEmergency {})?;
+    let config = CONFIG.load(deps.storage)?;
 
-    ADMINS.save(deps.storage, &admins)?;
+    let mut refund = Uint128::zero();
+    for round_id in emergency.voided_rounds {
+        let key = bet_info_key(round_id.u128(), &info.sender);
+        if let Some(game) = bet_info_storage().may_load(deps.storage, key.clone())? {
+            refund += game.amount;
+            bet_info_storage().remove(deps.storage, key)?;
+            /* The voided rounds never closed, so their bets weren't scored */
+            record_settlement(
+                deps.storage,
+                env.block.time,
+                &game,
+                Outcome::Refunded,
+                game.amount,
+                Uint128::zero(),
+            )?;
+        }
+    }
 
-    Ok(Response::new().add_attribute("add_admin", new_admin.to_string()))
+    if refund.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Nothing to withdraw",
+        )));
+    }
+    record_payout(
+        deps.storage,
+        &env,
+        refund,
+        Uint128::zero(),
+        &config.token_denom,
+    )?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(refund.u128(), &config.token_denom),
+        })
+        .add_attribute("action", "fuzio-emergency-withdraw")
+        .add_attribute("account", info.sender)
+        .add_attribute("amount", refund))
 }
 
-fn execute_remove_admin(
+fn execute_set_pause(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
-    old_admin: Addr,
+    betting: Option<bool>,
+    rounds: Option<bool>,
+    claims: Option<bool>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    let mut admins = ADMINS.load(deps.storage)?;
-    admins.retain(|admin| admin != old_admin);
+    assert_has_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+    let mut pause_state = PAUSE_STATE.load(deps.storage)?;
+    pause_state.betting = betting.unwrap_or(pause_state.betting);
+    pause_state.rounds = rounds.unwrap_or(pause_state.rounds);
+    pause_state.claims = claims.unwrap_or(pause_state.claims);
+    PAUSE_STATE.save(deps.storage, &pause_state)?;
+
+    Ok(Response::new().add_event(
+        Event::new("fuzio_pause")
+            .add_attribute("betting", pause_state.betting.to_string())
+            .add_attribute("rounds", pause_state.rounds.to_string())
+            .add_attribute("claims", pause_state.claims.to_string())
+            .add_attribute("sender", info.sender),
+    ))
+}
 
-    if admins.is_empty() {
-        return Err(ContractError::NeedOneAdmin {});
+/// Owners hold every role
+fn assert_has_role(
+    deps: Deps<SeiQueryWrapper>,
+    sender: &Addr,
+    role: Role,
+) -> Result<(), ContractError> {
+    let roles = ROLES
+        .may_load(deps.storage, sender.clone())?
+        .unwrap_or_default();
+    if !roles.contains(&Role::Owner) && !roles.contains(&role) {
+        return Err(ContractError::MissingRole {
+            role,
+            sender: sender.clone(),
+        });
     }
 
-    ADMINS.save(deps.storage, &admins)?;
-    Ok(Response::new().add_attribute("remove_admin", old_admin.to_string()))
+    Ok(())
 }
 
-fn execute_modify_dev_wallets(
+fn query_owners(storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+    ROLES
+        .range(storage, None, None, Order::Ascending)
+        .filter_map(|res| match res {
+            Ok((address, roles)) if roles.contains(&Role::Owner) => Some(Ok(address)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect()
+}
+
+pub(crate) fn grant_role(storage: &mut dyn Storage, address: &Addr, role: Role) -> StdResult<()> {
+    ROLES.update(storage, address.clone(), |roles| -> StdResult<_> {
+        let mut roles = roles.unwrap_or_default();
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+        Ok(roles)
+    })?;
+    Ok(())
+}
+
+fn revoke_role(storage: &mut dyn Storage, address: &Addr, role: Role) -> Result<(), ContractError> {
+    let mut roles = ROLES
+        .may_load(storage, address.clone())?
+        .unwrap_or_default();
+    roles.retain(|r| *r != role);
+    if roles.is_empty() {
+        ROLES.remove(storage, address.clone());
+    } else {
+        ROLES.save(storage, address.clone(), &roles)?;
+    }
+
+    /* The prices it pushed would keep counting towards the feeders' quorum */
+    if matches!(role, Role::OracleFeeder | Role::Owner)
+        && !roles.contains(&Role::OracleFeeder)
+        && !roles.contains(&Role::Owner)
+    {
+        let pushes = FEEDER_PRICES
+            .keys(storage, None, None, Order::Ascending)
+            .filter(|res| !matches!(res, Ok((_, feeder)) if feeder != address))
+            .collect::<StdResult<Vec<_>>>()?;
+        for (market, feeder) in pushes {
+            FEEDER_PRICES.remove(storage, (&market, feeder));
+        }
+    }
+
+    if role == Role::Owner {
+        if query_owners(storage)?.is_empty() {
+            return Err(ContractError::NeedOneAdmin {});
+        }
+        if matches!(GOVERNANCE.may_load(storage)?, Some(governance) if governance == *address) {
+            GOVERNANCE.remove(storage);
+        }
+    }
+
+    Ok(())
+}
+
+fn execute_grant_role(
     deps: DepsMut<SeiQueryWrapper>,
     info: MessageInfo,
-    new_wallets: Vec<WalletInfo>,
+    env: Env,
+    address: Addr,
+    role: Role,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    let mut total_ratio = Decimal::zero();
-    for dev_wallet in new_wallets.clone() {
-        total_ratio = total_ratio + dev_wallet.ratio;
+    /* New owners have to accept first */
+    if role == Role::Owner {
+        return execute_propose_ownership(
+            deps,
+            info,
+            env,
+            address,
+            OwnershipChange::AddAdmin,
+            None,
+        );
     }
 
-    if total_ratio != Decimal::one() {
-        return Err(ContractError::WrongRatio {});
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let address = deps.api.addr_validate(address.as_str())?;
+    grant_role(deps.storage, &address, role.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-grant-role")
+        .add_attribute("address", address)
+        .add_attribute("role", role.to_string()))
+}
+
+fn execute_revoke_role(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    address: Addr,
+    role: Role,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    revoke_role(deps.storage, &address, role.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "fuzio-revoke-role")
+        .add_attribute("address", address)
+        .add_attribute("role", role.to_string()))
+}
+
+fn execute_propose_ownership(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    candidate: Addr,
+    change: OwnershipChange,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let candidate = deps.api.addr_validate(candidate.as_str())?;
+
+    if query_owners(deps.storage)?.contains(&candidate) {
+        return Err(ContractError::AlreadyAdmin { address: candidate });
+    }
+    /* Only a contract can vote on anything */
+    if change == OwnershipChange::Governance
+        && deps
+            .querier
+            .query_wasm_contract_info(candidate.as_str())
+            .is_err()
+    {
+        return Err(ContractError::NotAContract { address: candidate });
+    }
+    if let Some(proposal) = OWNERSHIP_PROPOSALS.may_load(deps.storage, candidate.clone())? {
+        if env.block.time < proposal.expires_at {
+            return Err(ContractError::ProposalPending { candidate });
+        }
+    }
+
+    let proposal = OwnershipProposal {
+        candidate: candidate.clone(),
+        proposer: info.sender,
+        change,
+        expires_at: env
+            .block
+            .time
+            .plus_seconds(expires_in.unwrap_or(DEFAULT_PROPOSAL_EXPIRY)),
+    };
+    OWNERSHIP_PROPOSALS.save(deps.storage, candidate, &proposal)?;
+
+    Ok(Response::new().add_event(ownership_event("propose", &proposal)))
+}
+
+fn execute_accept_ownership(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let candidate = info.sender;
+    let proposal = OWNERSHIP_PROPOSALS
+        .may_load(deps.storage, candidate.clone())?
+        .ok_or_else(|| ContractError::NoProposal {
+            candidate: candidate.clone(),
+        })?;
+    OWNERSHIP_PROPOSALS.remove(deps.storage, candidate.clone());
+
+    if env.block.time >= proposal.expires_at {
+        return Err(ContractError::ProposalExpired { candidate });
+    }
+    /* The proposal only stands while the proposer is still an owner */
+    assert_has_role(deps.as_ref(), &proposal.proposer, Role::Owner)?;
+
+    grant_role(deps.storage, &candidate, Role::Owner)?;
+    match proposal.change {
+        OwnershipChange::AddAdmin => {}
+        OwnershipChange::Transfer => revoke_role(deps.storage, &proposal.proposer, Role::Owner)?,
+        OwnershipChange::Governance => {
+            /* Other owners keep their other roles */
+            for owner in query_owners(deps.storage)? {
+                if owner != candidate {
+                    revoke_role(deps.storage, &owner, Role::Owner)?;
+                }
+            }
+            GOVERNANCE.save(deps.storage, &candidate)?;
+        }
     }
 
-    let mut config = CONFIG.load(deps.storage)?;
-    config.dev_wallet_list = new_wallets;
-    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_event(ownership_event("accept", &proposal)))
+}
+
+fn execute_cancel_ownership_proposal(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    candidate: Addr,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    let proposal = OWNERSHIP_PROPOSALS
+        .may_load(deps.storage, candidate.clone())?
+        .ok_or_else(|| ContractError::NoProposal {
+            candidate: candidate.clone(),
+        })?;
+    OWNERSHIP_PROPOSALS.remove(deps.storage, candidate);
+
+    Ok(Response::new()
+        .add_event(ownership_event("cancel", &proposal).add_attribute("cancelled_by", info.sender)))
+}
+
+fn ownership_event(action: &str, proposal: &OwnershipProposal) -> Event {
+    Event::new("fuzio_ownership")
+        .add_attribute("action", action)
+        .add_attribute("change", proposal.change.to_string())
+        .add_attribute("proposer", proposal.proposer.to_string())
+        .add_attribute("candidate", proposal.candidate.to_string())
+        .add_attribute("expires_at", proposal.expires_at.seconds().to_string())
+}
+
+fn execute_remove_admin(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    old_admin: Addr,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::Owner)?;
+    revoke_role(deps.storage, &old_admin, Role::Owner)?;
+
+    Ok(Response::new()
+        .add_attribute("remove_admin", old_admin.to_string())
+        .add_event(
+            Event::new("fuzio_ownership")
+                .add_attribute("action", "remove")
+                .add_attribute("removed_by", info.sender)
+                .add_attribute("admin", old_admin),
+        ))
+}
+
+/// Changes the dev wallets like any other config field, timelock included
+fn execute_modify_dev_wallets(
+    deps: DepsMut<SeiQueryWrapper>,
+    info: MessageInfo,
+    env: Env,
+    new_wallets: Vec<WalletInfo>,
+) -> Result<Response, ContractError> {
+    assert_has_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+    let update = ConfigUpdate {
+        dev_wallet_list: Some(new_wallets),
+        ..ConfigUpdate::default()
+    };
+    let resp = propose_config_update(deps, env, info.sender, update)?;
 
-    Ok(Response::new().add_attribute("action", "new_dev_wallets"))
+    Ok(resp.add_attribute("action", "new_dev_wallets"))
 }
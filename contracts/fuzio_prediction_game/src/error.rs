@@ -1,7 +1,8 @@
 use cw0::PaymentError;
 use thiserror::Error;
 
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Addr, Decimal, StdError, Timestamp, Uint128};
+use fuzio_bet::fuzio_prediction_game::Role;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -20,12 +21,127 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("{action} is paused")]
+    Paused { action: String },
+
+    #[error("The contract has been shut down")]
+    EmergencyShutdown {},
+
+    #[error("The contract hasn't been shut down")]
+    NotInEmergency {},
+
     #[error("The sum of wallet ratio is not equal to 1")]
     WrongRatio {},
 
     #[error("Denom not found in oracle")]
     PriceNotFoundInOracle {},
 
+    #[error("Gaming fee can't be more than {max}")]
+    GamingFeeTooHigh { max: Uint128 },
+
+    #[error("{field} can't be zero")]
+    ZeroDuration { field: String },
+
+    #[error("Token denom can't be empty")]
+    EmptyDenom {},
+
     #[error("At least one admin must remain")]
     NeedOneAdmin {},
+
+    #[error("Missing role {role}. Sender: {sender}")]
+    MissingRole { role: Role, sender: Addr },
+
+    #[error("{address} is already an admin")]
+    AlreadyAdmin { address: Addr },
+
+    #[error("There is already a pending proposal for {candidate}")]
+    ProposalPending { candidate: Addr },
+
+    #[error("No pending proposal for {candidate}")]
+    NoProposal { candidate: Addr },
+
+    #[error("The proposal for {candidate} has expired")]
+    ProposalExpired { candidate: Addr },
+
+    #[error("No pending config change {id}")]
+    NoConfigChange { id: u64 },
+
+    #[error("Config change {id} can't be applied before {executable_at}")]
+    ConfigChangeTimelocked { id: u64, executable_at: Timestamp },
+
+    #[error("Winnings of round {round_id} can still be claimed")]
+    ClaimNotExpired { round_id: Uint128 },
+
+    #[error("No treasury configured")]
+    NoTreasury {},
+
+    #[error("{address} is not a contract")]
+    NotAContract { address: Addr },
+
+    #[error("Can only migrate from {contract} to the same contract")]
+    WrongContract { contract: String },
+
+    #[error("Invalid version {version}")]
+    InvalidVersion { version: String },
+
+    #[error("Can't migrate from {from} down to {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("A migration is still in progress, migrate again to finish it")]
+    MigrationPending {},
+
+    #[error("price_twap_samples has to be between 2 and {max}")]
+    InvalidTwapSamples { max: u32 },
+
+    #[error("Invalid price source: {reason}")]
+    InvalidPriceSource { reason: String },
+
+    #[error("Not enough price sources answered with a fresh price")]
+    QuorumNotMet {},
+
+    #[error("Unknown market {market}")]
+    UnknownMarket { market: String },
+
+    #[error("Price has to be positive")]
+    InvalidPrice {},
+
+    #[error("Price timestamp {timestamp} is in the future or older than the last push")]
+    InvalidPriceTimestamp { timestamp: Timestamp },
+
+    #[error("Price confidence interval {confidence} is wider than {max_confidence} of the price {price}")]
+    PriceTooUncertain {
+        price: Decimal,
+        confidence: Decimal,
+        max_confidence: Decimal,
+    },
+
+    #[error("Price published at {published} is too old")]
+    StalePrice { published: Timestamp },
+
+    #[error("No observation of the pair's price is {window_seconds} seconds old yet")]
+    NoAmmObservation { window_seconds: u64 },
+
+    #[error("Pool reserve {reserve} is below the minimum liquidity {min_liquidity}")]
+    LowLiquidity {
+        reserve: Uint128,
+        min_liquidity: Uint128,
+    },
+
+    #[error("Invalid season: {reason}")]
+    InvalidSeason { reason: String },
+
+    #[error("The season would overlap season {season_id}")]
+    SeasonOverlap { season_id: u64 },
+
+    #[error("Season {season_id} doesn't end before {end_time}")]
+    SeasonNotEnded { season_id: u64, end_time: Timestamp },
+
+    #[error("Season {season_id} has already been finalized")]
+    SeasonFinalized { season_id: u64 },
+
+    #[error("Season {season_id} has rounds left to close")]
+    SeasonRoundsOpen { season_id: u64 },
+
+    #[error("Season {season_id} has rounds left to score")]
+    SeasonRoundsUnscored { season_id: u64 },
 }
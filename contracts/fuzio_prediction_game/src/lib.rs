@@ -1,5 +1,6 @@
 pub mod contract;
 pub mod error;
+pub mod migrations;
 pub mod state;
 
 pub use fuzio_bet::fuzio_prediction_game::*;
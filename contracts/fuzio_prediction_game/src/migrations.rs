@@ -0,0 +1,143 @@
+use cosmwasm_std::{Binary, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
+use fuzio_bet::fuzio_prediction_game::{PauseState, Role};
+use semver::Version;
+
+use crate::contract::grant_role;
+use crate::error::ContractError;
+use crate::state::{claim_info_storage, ADMINS, IS_HALTED, MIGRATION_CURSOR, PAUSE_STATE};
+
+/// Whether a migration got through all of its data
+#[derive(Debug, PartialEq)]
+pub enum MigrationStep {
+    Done,
+    /* Ran out of its limit, the next migrate call carries on */
+    Pending,
+}
+
+/// A storage transform taking the storage to `version`. It may be run more
+/// than once when it is paginated, so it has to be able to pick up where it
+/// left off.
+pub struct Migration {
+    pub version: &'static str,
+    pub run: fn(&mut dyn Storage, u32) -> StdResult<MigrationStep>,
+}
+
+/* Ordered by version */
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: "1.1.0",
+    run: migrate_1_1_0,
+}];
+
+pub fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|_| ContractError::InvalidVersion {
+        version: version.to_string(),
+    })
+}
+
+/**
+ * Runs the migrations between the stored version and `to`, in order.
+ * Returns the version the storage is at, which is short of `to` when a
+ * migration ran out of its limit.
+ */
+pub fn run_migrations(
+    storage: &mut dyn Storage,
+    contract: &str,
+    to: &str,
+    limit: u32,
+) -> Result<(String, MigrationStep), ContractError> {
+    let stored = cw2::get_contract_version(storage)?;
+    if stored.contract != contract {
+        return Err(ContractError::WrongContract {
+            contract: stored.contract,
+        });
+    }
+    let from = parse_version(&stored.version)?;
+    let target = parse_version(to)?;
+    if from > target {
+        return Err(ContractError::CannotDowngrade {
+            from: stored.version,
+            to: to.to_string(),
+        });
+    }
+
+    let mut reached = stored.version;
+    for migration in MIGRATIONS {
+        let version = parse_version(migration.version)?;
+        if version <= from || version > target {
+            continue;
+        }
+        if (migration.run)(storage, limit)? == MigrationStep::Pending {
+            return Ok((reached, MigrationStep::Pending));
+        }
+        MIGRATION_CURSOR.remove(storage);
+        cw2::set_contract_version(storage, contract, migration.version)?;
+        reached = migration.version.to_string();
+    }
+
+    cw2::set_contract_version(storage, contract, to)?;
+    Ok((to.to_string(), MigrationStep::Done))
+}
+
+/// Storage is only usable once every migration has run
+pub fn assert_migrated(storage: &dyn Storage, version: &str) -> Result<(), ContractError> {
+    if cw2::get_contract_version(storage)?.version != version {
+        return Err(ContractError::MigrationPending {});
+    }
+    Ok(())
+}
+
+/**
+ * Moves the halt flag to the pause state and admins to the owner role, and
+ * records the player as claimant of the claims made before it was recorded
+ */
+fn migrate_1_1_0(storage: &mut dyn Storage, limit: u32) -> StdResult<MigrationStep> {
+    /* The single halt flag paused betting and rounds, claims stayed open */
+    if let Some(is_halted) = IS_HALTED.may_load(storage)? {
+        let pause_state = PauseState {
+            betting: is_halted,
+            rounds: is_halted,
+            claims: false,
+        };
+        PAUSE_STATE.save(storage, &pause_state)?;
+        IS_HALTED.remove(storage);
+    }
+
+    /* Admins become owners */
+    if let Some(admins) = ADMINS.may_load(storage)? {
+        for admin in admins {
+            grant_role(storage, &admin, Role::Owner)?;
+        }
+        ADMINS.remove(storage);
+    }
+
+    /* Players could only claim for themselves back then */
+    let limit = limit.max(1) as usize;
+    let start = MIGRATION_CURSOR
+        .may_load(storage)?
+        .map(|cursor| Bound::ExclusiveRaw(cursor.into()));
+    let claims = claim_info_storage()
+        .range_raw(storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let done = claims.len() <= limit;
+
+    for (key, mut claim) in claims.into_iter().take(limit) {
+        if claim.claimed_by.is_none() {
+            claim.claimed_by = Some(claim.player.clone());
+            claim.recipient = Some(claim.player.clone());
+            claim_info_storage().save(
+                storage,
+                (claim.round_id.u128(), claim.player.clone()),
+                &claim,
+            )?;
+        }
+        MIGRATION_CURSOR.save(storage, &Binary::from(key))?;
+    }
+
+    Ok(if done {
+        MigrationStep::Done
+    } else {
+        MigrationStep::Pending
+    })
+}
@@ -1,10 +1,22 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Binary, Decimal, Order, StdResult, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use fuzio_bet::fuzio_prediction_game::{BetInfoKey, BetInfo, ClaimInfoKey, ClaimInfo};
-use fuzio_bet::fuzio_prediction_game::{Config, FinishedRound, LiveRound, NextRound};
+use fuzio_bet::fuzio_prediction_game::{
+    Config, Direction, EmergencyState, FinishedRound, LiveRound, NextRound, OwnershipProposal, PauseState,
+    FeederPrice, GlobalStats, LeaderboardScore, PendingConfigChange, PlayerStats, PriceSample, Role, Season,
+};
 
+/* Superseded by PAUSE_STATE, only read when migrating */
 pub const IS_HALTED: Item<bool> = Item::new("is_halted");
+pub const PAUSE_STATE: Item<PauseState> = Item::new("pause_state");
+/* Set for good once an emergency shutdown is triggered */
+pub const EMERGENCY: Item<EmergencyState> = Item::new("emergency");
 pub const CONFIG: Item<Config> = Item::new("config");
+/* Config changes waiting for their timelock */
+pub const PENDING_CONFIG_CHANGES: Map<u64, PendingConfigChange> =
+    Map::new("pending_config_changes");
+pub const NEXT_CONFIG_CHANGE_ID: Item<u64> = Item::new("next_config_change_id");
 pub const NEXT_ROUND_ID: Item<u128> = Item::new("next_round_id");
 /* The round that's open for betting */
 pub const NEXT_ROUND: Item<NextRound> = Item::new("next_round");
@@ -12,10 +24,147 @@ pub const NEXT_ROUND: Item<NextRound> = Item::new("next_round");
 pub const LIVE_ROUND: Item<LiveRound> = Item::new("live_round");
 
 pub const ROUNDS: Map<u128, FinishedRound> = Map::new("rounds");
+/* Finished rounds whose bets are still being scored, with the last player
+scored, if any */
+pub const UNSCORED_ROUNDS: Map<u128, Option<Addr>> = Map::new("unscored_rounds");
+/* Summaries of pruned rounds, whose bets and claims have been deleted */
+pub const ARCHIVED_ROUNDS: Map<u128, ArchivedRound> = Map::new("archived_rounds");
+/* What the claims deleted so far of a round being pruned add up to */
+pub const PRUNED_CLAIMS: Map<u128, Uint128> = Map::new("pruned_claims");
+/* Keyed by the time of the sample in seconds */
+pub const PRICE_HISTORY: Map<u64, PriceSample> = Map::new("price_history");
+/* The last price each feeder pushed, by market and feeder */
+pub const FEEDER_PRICES: Map<(&str, Addr), FeederPrice> = Map::new("feeder_prices");
+/* The AMM price accumulators observed as rounds close, by pair, priced asset
+and time in seconds. Those before the one the TWAP would start from are dropped. */
+pub const AMM_OBSERVATIONS: Map<(&Addr, &str, u64), AmmObservation> =
+    Map::new("amm_observations");
 
+/* Superseded by ROLES, only read when migrating */
 pub const ADMINS: Item<Vec<Addr>> = Item::new("admins");
 
+/* Where a paginated migration stopped, as a raw primary key */
+pub const MIGRATION_CURSOR: Item<Binary> = Item::new("migration_cursor");
+
+pub const ROLES: Map<Addr, Vec<Role>> = Map::new("roles");
+/* Pending ownership proposals by candidate */
+pub const OWNERSHIP_PROPOSALS: Map<Addr, OwnershipProposal> = Map::new("ownership_proposals");
+/* The DAO or multisig contract that was handed ownership, if any */
+pub const GOVERNANCE: Item<Addr> = Item::new("governance");
+
 pub const TOTALS_SPENT: Map<Addr, Uint128> = Map::new("amounts_spent");
+pub const PLAYER_STATS: Map<Addr, PlayerStats> = Map::new("player_stats");
+pub const GLOBAL_STATS: Item<GlobalStats> = Item::new("global_stats");
+/* Keyed by days since the epoch */
+pub const DAILY_STATS: Map<u64, GlobalStats> = Map::new("daily_stats");
+
+/* Seasons don't overlap, so they are in chronological order */
+pub const SEASONS: Map<u64, Season> = Map::new("seasons");
+pub const NEXT_SEASON_ID: Item<u64> = Item::new("next_season_id");
+
+/// An AMM pair's price accumulator at some point
+#[cw_serde]
+pub struct AmmObservation {
+    pub time: Timestamp,
+    pub cumulative_price: Uint128,
+}
+
+/// What is kept of a finished round once its history has been pruned
+#[cw_serde]
+pub struct ArchivedRound {
+    pub bid_time: u64,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub open_price: Decimal,
+    pub close_price: Decimal,
+    pub winner: Option<Direction>,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    pub claimed_amount: Uint128,
+    #[serde(default)]
+    pub voided: bool,
+}
+
+impl ArchivedRound {
+    pub fn new(round: &FinishedRound, claimed_amount: Uint128) -> Self {
+        ArchivedRound {
+            bid_time: round.bid_time.seconds(),
+            open_time: round.open_time.seconds(),
+            close_time: round.close_time.seconds(),
+            open_price: round.open_price,
+            close_price: round.close_price,
+            winner: round.winner.clone(),
+            bull_amount: round.bull_amount,
+            bear_amount: round.bear_amount,
+            claimed_amount,
+            voided: round.voided,
+        }
+    }
+
+    pub fn into_finished_round(self, round_id: u128) -> FinishedRound {
+        FinishedRound {
+            id: Uint128::from(round_id),
+            bid_time: Timestamp::from_seconds(self.bid_time),
+            open_time: Timestamp::from_seconds(self.open_time),
+            close_time: Timestamp::from_seconds(self.close_time),
+            open_price: self.open_price,
+            close_price: self.close_price,
+            winner: self.winner,
+            bull_amount: self.bull_amount,
+            bear_amount: self.bear_amount,
+            pricing: None,
+            voided: self.voided,
+        }
+    }
+}
+
+/// Loads a finished round, whether it has been pruned or not
+pub fn may_load_finished_round(
+    storage: &dyn Storage,
+    round_id: u128,
+) -> StdResult<Option<FinishedRound>> {
+    if let Some(round) = ROUNDS.may_load(storage, round_id)? {
+        return Ok(Some(round));
+    }
+    Ok(ARCHIVED_ROUNDS
+        .may_load(storage, round_id)?
+        .map(|archived| archived.into_finished_round(round_id)))
+}
+
+/// Finished rounds after `start_after` in the given order, pruned or not
+pub fn range_finished_rounds<'a>(
+    storage: &'a dyn Storage,
+    start_after: Option<u128>,
+    order: Order,
+) -> impl Iterator<Item = StdResult<FinishedRound>> + 'a {
+    let bounds = || match order {
+        Order::Ascending => (start_after.map(Bound::exclusive), None),
+        Order::Descending => (None, start_after.map(Bound::exclusive)),
+    };
+    let (min, max) = bounds();
+    let mut rounds = ROUNDS.range(storage, min, max, order).peekable();
+    let (min, max) = bounds();
+    let mut archived = ARCHIVED_ROUNDS
+        .range(storage, min, max, order)
+        .map(|res| res.map(|(id, round)| (id, round.into_finished_round(id))))
+        .peekable();
+
+    /* Both maps are sorted by id and never hold the same round */
+    std::iter::from_fn(move || {
+        let from_rounds = match (rounds.peek(), archived.peek()) {
+            (None, None) => return None,
+            (Some(_), None) | (Some(Err(_)), _) => true,
+            (None, Some(_)) | (_, Some(Err(_))) => false,
+            (Some(Ok((a, _))), Some(Ok((b, _)))) => (a < b) == matches!(order, Order::Ascending),
+        };
+        let next = if from_rounds {
+            rounds.next()
+        } else {
+            archived.next()
+        };
+        next.map(|res| res.map(|(_, round)| round))
+    })
+}
 
 /// Convenience bid key constructor
 pub fn bet_info_key(round_id: u128, player: &Addr) -> BetInfoKey {
@@ -83,3 +232,47 @@ pub fn claim_info_storage<'a>() -> IndexedMap<'a, ClaimInfoKey, ClaimInfo, Claim
     };
     IndexedMap::new("claim_info", indexes)
 }
+
+/// Leaderboard scores are keyed by (window, player)
+pub type LeaderboardKey = (String, Addr);
+
+/// Defines indices for ranking the players of a window
+pub struct LeaderboardIndicies<'a> {
+    pub volume: MultiIndex<'a, (String, u128), LeaderboardScore, LeaderboardKey>,
+    pub net_pnl: MultiIndex<'a, (String, i128), LeaderboardScore, LeaderboardKey>,
+    pub win_streak: MultiIndex<'a, (String, u64), LeaderboardScore, LeaderboardKey>,
+}
+
+impl<'a> IndexList<LeaderboardScore> for LeaderboardIndicies<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<LeaderboardScore>> + '_> {
+        let v: Vec<&dyn Index<LeaderboardScore>> =
+            vec![&self.volume, &self.net_pnl, &self.win_streak];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn leaderboard_net_pnl(score: &LeaderboardScore) -> i128 {
+    score.won.u128() as i128 - score.settled_wagered.u128() as i128
+}
+
+pub fn leaderboard_storage<'a>(
+) -> IndexedMap<'a, LeaderboardKey, LeaderboardScore, LeaderboardIndicies<'a>> {
+    let indexes = LeaderboardIndicies {
+        volume: MultiIndex::new(
+            |_pk: &[u8], d: &LeaderboardScore| (d.window.clone(), d.volume.u128()),
+            "leaderboard",
+            "leaderboard_volume",
+        ),
+        net_pnl: MultiIndex::new(
+            |_pk: &[u8], d: &LeaderboardScore| (d.window.clone(), leaderboard_net_pnl(d)),
+            "leaderboard",
+            "leaderboard_net_pnl",
+        ),
+        win_streak: MultiIndex::new(
+            |_pk: &[u8], d: &LeaderboardScore| (d.window.clone(), d.best_win_streak),
+            "leaderboard",
+            "leaderboard_win_streak",
+        ),
+    };
+    IndexedMap::new("leaderboard", indexes)
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Uint128};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{ClaimInfoResponse, Direction, StatusResponse};
+use fuzio_prediction_game::error::ContractError;
+
+#[test]
+fn claim_and_bet_sends_back_what_isnt_bet() {
+    let mut suite = Suite::new();
+
+    /* Alice is owed 20000 less the 3% fee */
+    suite.play_first_round(
+        &[
+            (ALICE, Direction::Bull, 10_000),
+            (BOB, Direction::Bear, 10_000),
+        ],
+        120_000_000,
+    );
+    let alice = suite.balance(ALICE);
+    let owner = suite.balance(OWNER);
+    let game = suite.balance(suite.game.as_str());
+
+    /* She can't bet more than that */
+    assert_eq!(
+        suite
+            .execute(
+                ALICE,
+                ExecuteMsg::ClaimAndBet {
+                    direction: Direction::Bull,
+                    amount: Some(Uint128::new(19_401)),
+                },
+                &[],
+            )
+            .unwrap_err(),
+        ContractError::NotEnoughFunds {}
+    );
+    assert_eq!(suite.balance(ALICE), alice);
+
+    /* She bets 15000 of her 19400 on round 2 and gets the rest */
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimAndBet {
+                direction: Direction::Bull,
+                amount: Some(Uint128::new(15_000)),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(4_400));
+    assert_eq!(suite.balance(OWNER), owner + Uint128::new(600));
+    assert_eq!(
+        suite.balance(suite.game.as_str()),
+        game - Uint128::new(5_000)
+    );
+    let status: StatusResponse = suite.query(QueryMsg::Status {});
+    let bidding_round = status.bidding_round.unwrap();
+    assert_eq!(bidding_round.id, Uint128::new(2));
+    assert_eq!(bidding_round.bull_amount, Uint128::new(15_000));
+
+    /* Nothing is left to claim */
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimAndBet {
+                direction: Direction::Bull,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(4_400));
+}
+
+#[test]
+fn claim_and_bet_bets_everything_by_default() {
+    let mut suite = Suite::new();
+    suite.play_first_round(
+        &[
+            (ALICE, Direction::Bull, 10_000),
+            (BOB, Direction::Bear, 10_000),
+        ],
+        120_000_000,
+    );
+    let alice = suite.balance(ALICE);
+    let game = suite.balance(suite.game.as_str());
+
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::ClaimAndBet {
+                direction: Direction::Bear,
+                amount: None,
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice);
+    assert_eq!(suite.balance(suite.game.as_str()), game - Uint128::new(600));
+    let status: StatusResponse = suite.query(QueryMsg::Status {});
+    assert_eq!(
+        status.bidding_round.unwrap().bear_amount,
+        Uint128::new(19_400)
+    );
+}
+
+#[test]
+fn winnings_collected_for_a_player_are_paid_to_them() {
+    let mut suite = Suite::new();
+
+    /* Alice and Bob are each owed 20000 less the 3% fee */
+    suite.play_first_round(
+        &[
+            (ALICE, Direction::Bull, 10_000),
+            (BOB, Direction::Bull, 10_000),
+            (OWNER, Direction::Bear, 20_000),
+        ],
+        120_000_000,
+    );
+    let alice = suite.balance(ALICE);
+    let bob = suite.balance(BOB);
+    let carol = suite.balance(CAROL);
+
+    /* Carol relays Alice's claim, Alice gets the winnings */
+    suite
+        .execute(
+            CAROL,
+            ExecuteMsg::CollectWinningsFor {
+                player: Addr::unchecked(ALICE),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(19_400));
+    assert_eq!(suite.balance(CAROL), carol);
+
+    /* Bob has his sent to Carol */
+    suite
+        .execute(
+            BOB,
+            ExecuteMsg::CollectWinnings {
+                recipient: Some(Addr::unchecked(CAROL)),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance(BOB), bob);
+    assert_eq!(suite.balance(CAROL), carol + Uint128::new(19_400));
+
+    /* Both claims record who sent them and who was paid */
+    for (player, claimed_by, recipient) in [(ALICE, CAROL, ALICE), (BOB, BOB, CAROL)] {
+        let claims: ClaimInfoResponse = suite.query(QueryMsg::GetClaimInfoByUser {
+            player: Addr::unchecked(player),
+            start_after: None,
+            limit: None,
+        });
+        let claim = &claims.claim_info[0];
+        assert_eq!(claim.claimed_amount, Uint128::new(20_000));
+        assert_eq!(claim.claimed_by, Some(Addr::unchecked(claimed_by)));
+        assert_eq!(claim.recipient, Some(Addr::unchecked(recipient)));
+    }
+
+    /* Nothing is left for a second relay */
+    suite
+        .execute(
+            CAROL,
+            ExecuteMsg::CollectWinningsFor {
+                player: Addr::unchecked(ALICE),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(19_400));
+}
+
+#[test]
+fn expired_winnings_are_swept_a_page_at_a_time() {
+    let mut suite = Suite::with_config(|config| {
+        config.claim_expiry_seconds = Some(Uint128::new(1_000));
+        config.treasury = Some(Addr::unchecked(TREASURY));
+    })
+    .unwrap();
+
+    /* Alice, Bob and Carol are each owed 2000 */
+    suite.play_first_round(
+        &[
+            (ALICE, Direction::Bull, 1_000),
+            (BOB, Direction::Bull, 1_000),
+            (CAROL, Direction::Bull, 1_000),
+            (OWNER, Direction::Bear, 3_000),
+        ],
+        120_000_000,
+    );
+    let sweep = |suite: &mut Suite, sender: &str| {
+        suite.execute(
+            sender,
+            ExecuteMsg::SweepExpired {
+                round_ids: vec![Uint128::zero()],
+                limit: Some(1),
+            },
+            &[],
+        )
+    };
+    assert_eq!(
+        sweep(&mut suite, OWNER).unwrap_err(),
+        ContractError::ClaimNotExpired {
+            round_id: Uint128::zero(),
+        }
+    );
+
+    /* Bob claims in time, Alice too late */
+    suite
+        .execute(BOB, ExecuteMsg::CollectWinnings { recipient: None }, &[])
+        .unwrap();
+    suite.advance(1_000);
+    let alice = suite.balance(ALICE);
+    suite
+        .execute(ALICE, ExecuteMsg::CollectWinnings { recipient: None }, &[])
+        .unwrap_err();
+    assert_eq!(suite.balance(ALICE), alice);
+    assert_eq!(suite.balance(suite.game.as_str()), Uint128::new(4_000));
+
+    assert!(matches!(
+        sweep(&mut suite, CAROL).unwrap_err(),
+        ContractError::MissingRole { .. }
+    ));
+
+    /* One bet per call, Alice's, Carol's and then Owner's losing one */
+    for (swept, done) in [(2_000, "false"), (4_000, "false"), (4_000, "true")] {
+        let res = sweep(&mut suite, OWNER).unwrap();
+        let done_attr = res
+            .custom_attrs(1)
+            .iter()
+            .find(|attr| attr.key == "done")
+            .unwrap()
+            .value
+            .clone();
+        assert_eq!(done_attr, done);
+        assert_eq!(suite.balance(TREASURY), Uint128::new(swept));
+    }
+    assert_eq!(suite.balance(suite.game.as_str()), Uint128::zero());
+}
@@ -0,0 +1,279 @@
+#![allow(dead_code)]
+
+use cosmwasm_std::{
+    coins, from_binary, from_slice, to_binary, to_vec, Addr, Binary, Coin, Decimal, Deps, DepsMut,
+    Empty, Env, MessageInfo, Response, StdError, StdResult, Timestamp, Uint128, Uint64,
+};
+use cw_multi_test::{App, AppResponse, BasicAppBuilder, ContractWrapper, Executor};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{Config, Direction, PriceSource, WalletInfo};
+use fuzio_bet::pyth;
+use fuzio_prediction_game::error::ContractError;
+use sei_cosmwasm::SeiQueryWrapper;
+use serde::de::DeserializeOwned;
+
+pub const OWNER: &str = "owner";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+pub const CAROL: &str = "carol";
+pub const TREASURY: &str = "treasury";
+pub const DENOM: &str = "usei";
+pub const FEED_ID: &str = "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+pub const ROUND_SECONDS: u64 = 600;
+
+pub type SeiApp = App<
+    cw_multi_test::BankKeeper,
+    cosmwasm_std::testing::MockApi,
+    cosmwasm_std::testing::MockStorage,
+    cw_multi_test::FailingModule<Empty, SeiQueryWrapper, Empty>,
+    cw_multi_test::WasmKeeper<Empty, SeiQueryWrapper>,
+>;
+
+/*
+ * A stand-in for the Pyth contract serving a single feed, whose price is set
+ * by an update holding the new one as JSON
+ */
+
+const MOCK_PRICE_KEY: &[u8] = b"price";
+
+fn mock_pyth_instantiate(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: pyth::Price,
+) -> StdResult<Response> {
+    deps.storage.set(MOCK_PRICE_KEY, &to_vec(&msg)?);
+    Ok(Response::new())
+}
+
+fn mock_pyth_execute(
+    deps: DepsMut<SeiQueryWrapper>,
+    _env: Env,
+    _info: MessageInfo,
+    msg: pyth::ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        pyth::ExecuteMsg::UpdatePriceFeeds { data } => {
+            for update in data {
+                let price: pyth::Price = from_binary(&update)?;
+                deps.storage.set(MOCK_PRICE_KEY, &to_vec(&price)?);
+            }
+            Ok(Response::new())
+        }
+    }
+}
+
+fn mock_pyth_query(
+    deps: Deps<SeiQueryWrapper>,
+    _env: Env,
+    msg: pyth::QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        pyth::QueryMsg::PriceFeed { id } => {
+            if id != FEED_ID {
+                return Err(StdError::not_found("price feed"));
+            }
+            let price: pyth::Price = from_slice(&deps.storage.get(MOCK_PRICE_KEY).unwrap())?;
+            to_binary(&pyth::PriceFeedResponse {
+                price_feed: pyth::PriceFeed {
+                    id,
+                    price: price.clone(),
+                    ema_price: price,
+                },
+            })
+        }
+    }
+}
+
+/// A price with 8 decimals, published at `publish_time`
+pub fn pyth_price(price: u64, conf: u64, publish_time: Timestamp) -> pyth::Price {
+    pyth::Price {
+        price: Uint64::new(price),
+        conf: Uint64::new(conf),
+        expo: -8,
+        publish_time: publish_time.seconds() as i64,
+    }
+}
+
+/// A game priced by the Pyth mock, whose players each start with 100_000 usei
+pub struct Suite {
+    pub app: SeiApp,
+    pub game: Addr,
+    pub pyth: Addr,
+}
+
+impl Suite {
+    pub fn new() -> Self {
+        Suite::with_config(|_| {}).unwrap()
+    }
+
+    /// Instantiates the game with the default config as changed by `update`
+    pub fn with_config(update: impl FnOnce(&mut Config)) -> Result<Self, ContractError> {
+        let mut app =
+            BasicAppBuilder::<Empty, SeiQueryWrapper>::new_custom().build(|router, _, storage| {
+                for player in [OWNER, ALICE, BOB, CAROL] {
+                    router
+                        .bank
+                        .init_balance(storage, &Addr::unchecked(player), coins(100_000, DENOM))
+                        .unwrap();
+                }
+            });
+
+        let game_code = app.store_code(Box::new(
+            ContractWrapper::new(
+                fuzio_prediction_game::contract::execute,
+                fuzio_prediction_game::contract::instantiate,
+                fuzio_prediction_game::contract::query,
+            )
+            .with_reply(fuzio_prediction_game::contract::reply),
+        ));
+        let pyth_code = app.store_code(Box::new(ContractWrapper::new(
+            mock_pyth_execute,
+            mock_pyth_instantiate,
+            mock_pyth_query,
+        )));
+
+        let now = app.block_info().time;
+        let pyth = app
+            .instantiate_contract(
+                pyth_code,
+                Addr::unchecked(OWNER),
+                &pyth_price(100_000_000, 100_000, now),
+                &[],
+                "pyth",
+                None,
+            )
+            .unwrap();
+
+        let mut config = Config {
+            next_round_seconds: Uint128::new(ROUND_SECONDS.into()),
+            minimum_bet: Uint128::new(1_000),
+            gaming_fee: Uint128::new(300),
+            token_denom: DENOM.to_string(),
+            bet_token_denom: "uatom".to_string(),
+            dev_wallet_list: vec![WalletInfo {
+                address: Addr::unchecked(OWNER),
+                ratio: Decimal::one(),
+            }],
+            claim_expiry_seconds: None,
+            treasury: None,
+            config_timelock_seconds: None,
+            price_twap_samples: None,
+            price_source: Some(PriceSource::Pyth {
+                contract: pyth.clone(),
+                feed_id: FEED_ID.to_string(),
+                max_confidence: Decimal::percent(1),
+                max_age_seconds: 60,
+            }),
+        };
+        update(&mut config);
+
+        let game = app
+            .instantiate_contract(
+                game_code,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg { config },
+                &[],
+                "fuzio prediction game",
+                None,
+            )
+            .map_err(|err| err.downcast::<ContractError>().unwrap())?;
+
+        Ok(Suite { app, game, pyth })
+    }
+
+    pub fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += seconds / 5;
+        });
+    }
+
+    pub fn now(&self) -> Timestamp {
+        self.app.block_info().time
+    }
+
+    pub fn set_price(&mut self, price: pyth::Price) {
+        self.app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                self.pyth.clone(),
+                &pyth::ExecuteMsg::UpdatePriceFeeds {
+                    data: vec![to_binary(&price).unwrap()],
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    pub fn close_round(&mut self) -> Result<AppResponse, ContractError> {
+        self.execute(
+            OWNER,
+            ExecuteMsg::CloseRound {
+                price_updates: None,
+            },
+            &[],
+        )
+    }
+
+    /// Publishes `price` (8 decimals) now and closes the round
+    pub fn close_round_at(&mut self, price: u64) {
+        self.set_price(pyth_price(price, 0, self.now()));
+        self.close_round().unwrap();
+    }
+
+    /// Plays round 0 with `bets`, locking it at 1.00 and closing it at
+    /// `close_price` (8 decimals)
+    pub fn play_first_round(&mut self, bets: &[(&str, Direction, u128)], close_price: u64) {
+        self.close_round_at(100_000_000);
+        for (player, direction, amount) in bets {
+            self.bet(player, 0, direction.clone(), *amount).unwrap();
+        }
+        self.advance(ROUND_SECONDS);
+        self.close_round_at(100_000_000);
+        self.advance(ROUND_SECONDS);
+        self.close_round_at(close_price);
+    }
+
+    pub fn execute(
+        &mut self,
+        sender: &str,
+        msg: ExecuteMsg,
+        funds: &[Coin],
+    ) -> Result<AppResponse, ContractError> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.game.clone(), &msg, funds)
+            .map_err(|err| err.downcast::<ContractError>().unwrap())
+    }
+
+    pub fn bet(
+        &mut self,
+        player: &str,
+        round_id: u128,
+        direction: Direction,
+        amount: u128,
+    ) -> Result<AppResponse, ContractError> {
+        let round_id = Uint128::new(round_id);
+        let amount = Uint128::new(amount);
+        let msg = match direction {
+            Direction::Bull => ExecuteMsg::BetBull { round_id, amount },
+            Direction::Bear => ExecuteMsg::BetBear { round_id, amount },
+        };
+        self.execute(player, msg, &coins(amount.u128(), DENOM))
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: QueryMsg) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.game.clone(), &msg)
+            .unwrap()
+    }
+
+    pub fn balance(&self, address: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_balance(address, DENOM)
+            .unwrap()
+            .amount
+    }
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{
+    ConfigResponse, ConfigUpdate, OptionalConfigField, Role, WalletInfo,
+};
+use fuzio_prediction_game::error::ContractError;
+
+#[test]
+fn optional_config_fields_can_be_cleared() {
+    let mut suite = Suite::new();
+
+    let mut update_config = |update: ConfigUpdate| {
+        suite
+            .execute(
+                OWNER,
+                ExecuteMsg::UpdateConfig {
+                    config: Box::new(update),
+                },
+                &[],
+            )
+            .unwrap();
+        let config: ConfigResponse = suite.query(QueryMsg::Config {});
+        config
+    };
+
+    let config = update_config(ConfigUpdate {
+        treasury: Some(Addr::unchecked(ALICE)),
+        price_twap_samples: Some(5),
+        ..ConfigUpdate::default()
+    });
+    assert_eq!(config.treasury, Some(Addr::unchecked(ALICE)));
+    assert_eq!(config.price_twap_samples, Some(5));
+
+    /* Clearing is done first, so a field both cleared and given is set */
+    let config = update_config(ConfigUpdate {
+        treasury: Some(Addr::unchecked(BOB)),
+        clear: Some(vec![
+            OptionalConfigField::Treasury,
+            OptionalConfigField::PriceTwapSamples,
+        ]),
+        ..ConfigUpdate::default()
+    });
+    assert_eq!(config.treasury, Some(Addr::unchecked(BOB)));
+    assert_eq!(config.price_twap_samples, None);
+
+    let config = update_config(ConfigUpdate {
+        clear: Some(vec![OptionalConfigField::Treasury]),
+        ..ConfigUpdate::default()
+    });
+    assert_eq!(config.treasury, None);
+}
+
+#[test]
+fn only_fee_managers_redirect_fees() {
+    let mut suite = Suite::new();
+    for (player, role) in [(ALICE, Role::ConfigManager), (BOB, Role::FeeManager)] {
+        suite
+            .execute(
+                OWNER,
+                ExecuteMsg::GrantRole {
+                    address: Addr::unchecked(player),
+                    role,
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    /* A config manager can change the fee, but not where it goes */
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::UpdateConfig {
+                config: Box::new(ConfigUpdate {
+                    gaming_fee: Some(Uint128::new(200)),
+                    ..ConfigUpdate::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    for update in [
+        ConfigUpdate {
+            treasury: Some(Addr::unchecked(ALICE)),
+            ..ConfigUpdate::default()
+        },
+        ConfigUpdate {
+            dev_wallet_list: Some(vec![WalletInfo {
+                address: Addr::unchecked(ALICE),
+                ratio: Decimal::one(),
+            }]),
+            ..ConfigUpdate::default()
+        },
+    ] {
+        assert_eq!(
+            suite
+                .execute(
+                    ALICE,
+                    ExecuteMsg::UpdateConfig {
+                        config: Box::new(update),
+                    },
+                    &[],
+                )
+                .unwrap_err(),
+            ContractError::MissingRole {
+                role: Role::FeeManager,
+                sender: Addr::unchecked(ALICE),
+            }
+        );
+    }
+
+    /* Nor drop a queued change of the dev wallets, which a fee manager can */
+    suite
+        .execute(
+            ALICE,
+            ExecuteMsg::UpdateConfig {
+                config: Box::new(ConfigUpdate {
+                    config_timelock_seconds: Some(Uint128::new(3600)),
+                    ..ConfigUpdate::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .execute(
+            BOB,
+            ExecuteMsg::ModifyDevWallet {
+                new_dev_wallets: vec![WalletInfo {
+                    address: Addr::unchecked(BOB),
+                    ratio: Decimal::one(),
+                }],
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(
+        suite
+            .execute(ALICE, ExecuteMsg::CancelConfigChange { id: 0 }, &[])
+            .unwrap_err(),
+        ContractError::MissingRole {
+            role: Role::FeeManager,
+            sender: Addr::unchecked(ALICE),
+        }
+    );
+    suite
+        .execute(BOB, ExecuteMsg::CancelConfigChange { id: 0 }, &[])
+        .unwrap();
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Uint128;
+use fuzio_bet::fuzio_prediction_game::msg::ExecuteMsg;
+use fuzio_bet::fuzio_prediction_game::Direction;
+use fuzio_prediction_game::error::ContractError;
+
+#[test]
+fn stakes_of_voided_rounds_are_withdrawn_after_shutdown() {
+    let mut suite = Suite::new();
+    assert_eq!(
+        suite
+            .execute(ALICE, ExecuteMsg::EmergencyWithdraw {}, &[])
+            .unwrap_err(),
+        ContractError::NotInEmergency {}
+    );
+
+    /* Alice has bets in the live round 0 and the bidding round 1, Bob in 1 */
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 0, Direction::Bull, 5_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 1, Direction::Bear, 2_000).unwrap();
+    suite.bet(BOB, 1, Direction::Bull, 3_000).unwrap();
+
+    assert!(matches!(
+        suite
+            .execute(ALICE, ExecuteMsg::EmergencyShutdown {}, &[])
+            .unwrap_err(),
+        ContractError::MissingRole { .. }
+    ));
+    suite
+        .execute(OWNER, ExecuteMsg::EmergencyShutdown {}, &[])
+        .unwrap();
+    assert_eq!(
+        suite.bet(CAROL, 1, Direction::Bull, 1_000).unwrap_err(),
+        ContractError::EmergencyShutdown {}
+    );
+
+    /* Both get their full stakes back, free of fees, once */
+    let alice = suite.balance(ALICE);
+    let bob = suite.balance(BOB);
+    let owner = suite.balance(OWNER);
+    suite
+        .execute(ALICE, ExecuteMsg::EmergencyWithdraw {}, &[])
+        .unwrap();
+    suite
+        .execute(BOB, ExecuteMsg::EmergencyWithdraw {}, &[])
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(7_000));
+    assert_eq!(suite.balance(BOB), bob + Uint128::new(3_000));
+    assert_eq!(suite.balance(OWNER), owner);
+    assert_eq!(suite.balance(suite.game.as_str()), Uint128::zero());
+
+    suite
+        .execute(ALICE, ExecuteMsg::EmergencyWithdraw {}, &[])
+        .unwrap_err();
+    suite
+        .execute(CAROL, ExecuteMsg::EmergencyWithdraw {}, &[])
+        .unwrap_err();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(7_000));
+}
+
+#[test]
+fn winnings_of_finished_rounds_survive_a_shutdown() {
+    let mut suite = Suite::new();
+    suite.play_first_round(
+        &[
+            (ALICE, Direction::Bull, 10_000),
+            (BOB, Direction::Bear, 10_000),
+        ],
+        120_000_000,
+    );
+    suite
+        .execute(OWNER, ExecuteMsg::EmergencyShutdown {}, &[])
+        .unwrap();
+
+    let alice = suite.balance(ALICE);
+    suite
+        .execute(ALICE, ExecuteMsg::CollectWinnings { recipient: None }, &[])
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(19_400));
+
+    /* Round 0 finished, so it isn't refunded */
+    suite
+        .execute(BOB, ExecuteMsg::EmergencyWithdraw {}, &[])
+        .unwrap_err();
+}
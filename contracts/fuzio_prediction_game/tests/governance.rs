@@ -0,0 +1,475 @@
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::{
+    from_slice, Addr, Api, Binary, BlockInfo, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Querier, QuerierWrapper, Reply, Response, Storage, Uint128, Uint64,
+};
+use cw3::Vote;
+use cw_multi_test::{
+    App, AppResponse, BankKeeper, BasicAppBuilder, Contract, ContractWrapper, CosmosRouter,
+    DistributionKeeper, Executor, FailingModule, Module, StakeKeeper, WasmKeeper,
+};
+use cw_utils::{Duration, Threshold};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{
+    Config, GovernanceResponse, PauseStateResponse, Role, RolesResponse, WalletInfo,
+};
+use fuzio_prediction_game::error::ContractError;
+use schemars::JsonSchema;
+use sei_cosmwasm::{
+    DenomOracleExchangeRatePair, ExchangeRatesResponse, OracleExchangeRate, SeiQuery,
+    SeiQueryWrapper,
+};
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+const OWNER: &str = "owner";
+const ALICE: &str = "alice";
+const BOB: &str = "bob";
+
+type SeiApp = App<
+    BankKeeper,
+    cosmwasm_std::testing::MockApi,
+    cosmwasm_std::testing::MockStorage,
+    SeiOracle,
+    WasmKeeper<Empty, SeiQueryWrapper>,
+    StakeKeeper,
+    DistributionKeeper,
+    FailingModule<cosmwasm_std::IbcMsg, cosmwasm_std::IbcQuery, Empty>,
+    FailingModule<cosmwasm_std::GovMsg, Empty, Empty>,
+>;
+
+/// Answers the oracle exchange rate query with a fixed price
+struct SeiOracle;
+
+impl Module for SeiOracle {
+    type ExecT = Empty;
+    type QueryT = SeiQueryWrapper;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: cosmwasm_std::CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Unexpected custom exec {:?}", msg)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: cosmwasm_std::CustomQuery + DeserializeOwned + 'static,
+    {
+        bail!("Unexpected custom sudo {:?}", msg)
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        block: &BlockInfo,
+        request: SeiQueryWrapper,
+    ) -> AnyResult<Binary> {
+        match request.query_data {
+            SeiQuery::ExchangeRates {} => Ok(cosmwasm_std::to_binary(&ExchangeRatesResponse {
+                denom_oracle_exchange_rate_pairs: vec![DenomOracleExchangeRatePair {
+                    denom: "usei".to_string(),
+                    oracle_exchange_rate: OracleExchangeRate {
+                        exchange_rate: Decimal::percent(25),
+                        last_update: Uint64::new(block.height),
+                    },
+                }],
+            })?),
+            other => bail!("Unexpected sei query {:?}", other),
+        }
+    }
+}
+
+/// cw3-fixed-multisig only queries the chain's standard modules, so it can run
+/// on an app whose contracts use Sei's custom queries
+struct Multisig;
+
+impl Contract<Empty, SeiQueryWrapper> for Multisig {
+    fn execute(
+        &self,
+        deps: DepsMut<SeiQueryWrapper>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response> {
+        let querier = QuerierWrapper::<Empty>::new(&*deps.querier);
+        let deps = DepsMut {
+            storage: deps.storage,
+            api: deps.api,
+            querier,
+        };
+        Ok(cw3_fixed_multisig::contract::execute(
+            deps,
+            env,
+            info,
+            from_slice(&msg)?,
+        )?)
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut<SeiQueryWrapper>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response> {
+        let querier = QuerierWrapper::<Empty>::new(&*deps.querier);
+        let deps = DepsMut {
+            storage: deps.storage,
+            api: deps.api,
+            querier,
+        };
+        Ok(cw3_fixed_multisig::contract::instantiate(
+            deps,
+            env,
+            info,
+            from_slice(&msg)?,
+        )?)
+    }
+
+    fn query(&self, deps: Deps<SeiQueryWrapper>, env: Env, msg: Vec<u8>) -> AnyResult<Binary> {
+        let deps = Deps {
+            storage: deps.storage,
+            api: deps.api,
+            querier: QuerierWrapper::<Empty>::new(&*deps.querier),
+        };
+        Ok(cw3_fixed_multisig::contract::query(
+            deps,
+            env,
+            from_slice(&msg)?,
+        )?)
+    }
+
+    fn sudo(
+        &self,
+        _deps: DepsMut<SeiQueryWrapper>,
+        _env: Env,
+        _msg: Vec<u8>,
+    ) -> AnyResult<Response> {
+        bail!("cw3-fixed-multisig has no sudo")
+    }
+
+    fn reply(
+        &self,
+        _deps: DepsMut<SeiQueryWrapper>,
+        _env: Env,
+        _msg: Reply,
+    ) -> AnyResult<Response> {
+        bail!("cw3-fixed-multisig has no reply")
+    }
+
+    fn migrate(
+        &self,
+        _deps: DepsMut<SeiQueryWrapper>,
+        _env: Env,
+        _msg: Vec<u8>,
+    ) -> AnyResult<Response> {
+        bail!("cw3-fixed-multisig has no migrate")
+    }
+}
+
+struct Suite {
+    app: SeiApp,
+    game: Addr,
+    multisig: Addr,
+}
+
+impl Suite {
+    fn new() -> Self {
+        let mut app = BasicAppBuilder::<Empty, SeiQueryWrapper>::new_custom()
+            .with_custom(SeiOracle)
+            .build(|_, _, _| {});
+
+        let game_code = app.store_code(Box::new(ContractWrapper::new(
+            fuzio_prediction_game::contract::execute,
+            fuzio_prediction_game::contract::instantiate,
+            fuzio_prediction_game::contract::query,
+        )));
+        let multisig_code = app.store_code(Box::new(Multisig));
+
+        let game = app
+            .instantiate_contract(
+                game_code,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    config: Config {
+                        next_round_seconds: Uint128::new(600),
+                        minimum_bet: Uint128::new(1_000),
+                        gaming_fee: Uint128::new(300),
+                        token_denom: "usei".to_string(),
+                        bet_token_denom: "usei".to_string(),
+                        dev_wallet_list: vec![WalletInfo {
+                            address: Addr::unchecked(OWNER),
+                            ratio: Decimal::one(),
+                        }],
+                        claim_expiry_seconds: None,
+                        treasury: None,
+                        config_timelock_seconds: None,
+                        price_twap_samples: None,
+                        price_source: None,
+                    },
+                },
+                &[],
+                "fuzio prediction game",
+                None,
+            )
+            .unwrap();
+
+        let multisig = app
+            .instantiate_contract(
+                multisig_code,
+                Addr::unchecked(OWNER),
+                &cw3_fixed_multisig::msg::InstantiateMsg {
+                    voters: vec![
+                        cw3_fixed_multisig::msg::Voter {
+                            addr: ALICE.to_string(),
+                            weight: 1,
+                        },
+                        cw3_fixed_multisig::msg::Voter {
+                            addr: BOB.to_string(),
+                            weight: 1,
+                        },
+                    ],
+                    threshold: Threshold::AbsoluteCount { weight: 2 },
+                    max_voting_period: Duration::Time(3600),
+                },
+                &[],
+                "multisig",
+                None,
+            )
+            .unwrap();
+
+        Suite {
+            app,
+            game,
+            multisig,
+        }
+    }
+
+    /// Run `msg` against the game through a multisig proposal both voters approve
+    fn pass_proposal(&mut self, title: &str, msg: ExecuteMsg) -> AnyResult<AppResponse> {
+        let res = self.app.execute_contract(
+            Addr::unchecked(ALICE),
+            self.multisig.clone(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Propose {
+                title: title.to_string(),
+                description: title.to_string(),
+                msgs: vec![msg.into_cosmos_msg(self.game.as_str())?],
+                latest: None,
+            },
+            &[],
+        )?;
+        let proposal_id: u64 = res
+            .custom_attrs(1)
+            .iter()
+            .find(|attr| attr.key == "proposal_id")
+            .map(|attr| attr.value.parse())
+            .unwrap()?;
+
+        self.app.execute_contract(
+            Addr::unchecked(BOB),
+            self.multisig.clone(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Vote {
+                proposal_id,
+                vote: Vote::Yes,
+            },
+            &[],
+        )?;
+        self.app.execute_contract(
+            Addr::unchecked(BOB),
+            self.multisig.clone(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+    }
+
+    fn roles(&self, address: &str) -> Vec<Role> {
+        let res: RolesResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                self.game.clone(),
+                &QueryMsg::Roles {
+                    address: Addr::unchecked(address),
+                },
+            )
+            .unwrap();
+        res.roles
+    }
+}
+
+#[test]
+fn multisig_takes_over_ownership() {
+    let mut suite = Suite::new();
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::GrantRole {
+                address: Addr::unchecked(BOB),
+                role: Role::Pauser,
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::HandOverToGovernance {
+                governance: suite.multisig.clone(),
+                expires_in: None,
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .pass_proposal("Accept ownership", ExecuteMsg::AcceptOwnership {})
+        .unwrap();
+
+    let governance: GovernanceResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.game.clone(), &QueryMsg::Governance {})
+        .unwrap();
+    assert_eq!(governance.governance, Some(suite.multisig.clone()));
+    assert_eq!(suite.roles(suite.multisig.as_str()), vec![Role::Owner]);
+    assert_eq!(suite.roles(OWNER), vec![]);
+    /* Other roles are left alone */
+    assert_eq!(suite.roles(BOB), vec![Role::Pauser]);
+
+    /* The previous owner can't act anymore */
+    let err = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::SetPause {
+                betting: Some(true),
+                rounds: None,
+                claims: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::MissingRole {
+            role: Role::Pauser,
+            sender: Addr::unchecked(OWNER),
+        }
+    );
+
+    /* But a passed proposal can */
+    suite
+        .pass_proposal(
+            "Pause betting",
+            ExecuteMsg::SetPause {
+                betting: Some(true),
+                rounds: None,
+                claims: None,
+            },
+        )
+        .unwrap();
+    let pause_state: PauseStateResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.game.clone(), &QueryMsg::PauseState {})
+        .unwrap();
+    assert!(pause_state.betting);
+    assert!(!pause_state.rounds);
+}
+
+#[test]
+fn governance_must_be_a_contract() {
+    let mut suite = Suite::new();
+
+    let err = suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::HandOverToGovernance {
+                governance: Addr::unchecked(ALICE),
+                expires_in: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<ContractError>().unwrap(),
+        ContractError::NotAContract {
+            address: Addr::unchecked(ALICE),
+        }
+    );
+}
+
+#[test]
+fn proposal_needs_every_vote() {
+    let mut suite = Suite::new();
+
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::HandOverToGovernance {
+                governance: suite.multisig.clone(),
+                expires_in: None,
+            },
+            &[],
+        )
+        .unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            suite.multisig.clone(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Propose {
+                title: "Accept ownership".to_string(),
+                description: "Accept ownership".to_string(),
+                msgs: vec![ExecuteMsg::AcceptOwnership {}
+                    .into_cosmos_msg(suite.game.as_str())
+                    .unwrap()],
+                latest: None,
+            },
+            &[],
+        )
+        .unwrap();
+    /* Only one of two votes is in */
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(ALICE),
+            suite.multisig.clone(),
+            &cw3_fixed_multisig::msg::ExecuteMsg::Execute { proposal_id: 1 },
+            &[],
+        )
+        .unwrap_err();
+
+    assert_eq!(suite.roles(OWNER), vec![Role::Owner]);
+    assert_eq!(suite.roles(suite.multisig.as_str()), vec![]);
+}
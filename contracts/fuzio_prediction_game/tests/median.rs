@@ -0,0 +1,85 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{Direction, PriceSource, Role, RoundResponse};
+
+fn push_price(suite: &mut Suite, feeder: &str, price: Decimal) {
+    let timestamp = suite.now();
+    suite
+        .execute(
+            feeder,
+            ExecuteMsg::PushPrice {
+                market: "uatom".to_string(),
+                price,
+                timestamp,
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+#[test]
+fn rounds_without_a_quorum_are_voided_and_refunded() {
+    /* The median of the feeders and Pyth, which both have to answer */
+    let mut suite = Suite::with_config(|config| {
+        let pyth = config.price_source.take().unwrap();
+        config.price_source = Some(PriceSource::Median {
+            sources: vec![
+                PriceSource::Feeders {
+                    quorum: 2,
+                    max_age_seconds: 60,
+                    tolerance: None,
+                },
+                pyth,
+            ],
+            quorum: 2,
+            max_age_seconds: None,
+        });
+    })
+    .unwrap();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::GrantRole {
+                address: Addr::unchecked(ALICE),
+                role: Role::OracleFeeder,
+            },
+            &[],
+        )
+        .unwrap();
+
+    /* Round 0 locks with both feeders and Pyth at 1.00 */
+    suite.close_round_at(100_000_000);
+    suite.bet(BOB, 0, Direction::Bull, 5_000).unwrap();
+    suite.bet(CAROL, 0, Direction::Bear, 5_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    push_price(&mut suite, ALICE, Decimal::one());
+    push_price(&mut suite, OWNER, Decimal::one());
+    suite.close_round_at(100_000_000);
+
+    /* Only one feeder is fresh when it closes, so only Pyth answers */
+    suite.advance(ROUND_SECONDS);
+    push_price(&mut suite, ALICE, Decimal::percent(120));
+    suite.close_round_at(120_000_000);
+    let round: RoundResponse = suite.query(QueryMsg::FinishedRound {
+        round_id: Uint128::zero(),
+    });
+    assert!(round.voided);
+    assert_eq!(round.winner, None);
+
+    /* Both bets are refunded in full, the dev wallet takes no fee */
+    let bob = suite.balance(BOB);
+    let carol = suite.balance(CAROL);
+    let owner = suite.balance(OWNER);
+    for player in [BOB, CAROL] {
+        suite
+            .execute(player, ExecuteMsg::CollectWinnings { recipient: None }, &[])
+            .unwrap();
+    }
+    assert_eq!(suite.balance(BOB), bob + Uint128::new(5_000));
+    assert_eq!(suite.balance(CAROL), carol + Uint128::new(5_000));
+    assert_eq!(suite.balance(OWNER), owner);
+    assert_eq!(suite.balance(suite.game.as_str()), Uint128::zero());
+}
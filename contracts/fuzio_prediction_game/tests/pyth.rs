@@ -0,0 +1,213 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coin, to_binary, Addr, Decimal, Timestamp, Uint128};
+use cw_multi_test::Executor;
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{
+    ConfigUpdate, Direction, PriceHistoryResponse, PriceSource, RoundResponse, StatusResponse,
+};
+use fuzio_prediction_game::error::ContractError;
+
+#[test]
+fn rounds_settle_on_the_pyth_price() {
+    let mut suite = Suite::new();
+
+    /* Open the bidding, then lock it at 1.00 */
+    suite.close_round().unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.set_price(pyth_price(100_000_000, 100_000, suite.now()));
+    suite.close_round().unwrap();
+    let status: StatusResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.game.clone(), &QueryMsg::Status {})
+        .unwrap();
+    assert_eq!(status.live_round.unwrap().open_price, Decimal::one());
+
+    /* 1.20 +- 0.06 is too uncertain to settle on */
+    suite.advance(ROUND_SECONDS);
+    suite.set_price(pyth_price(120_000_000, 6_000_000, suite.now()));
+    assert_eq!(
+        suite.close_round().unwrap_err(),
+        ContractError::PriceTooUncertain {
+            price: Decimal::percent(120),
+            confidence: Decimal::percent(6),
+            max_confidence: Decimal::percent(1),
+        }
+    );
+
+    /* So is a price from two minutes ago */
+    let published = Timestamp::from_seconds(suite.now().seconds() - 120);
+    suite.set_price(pyth_price(120_000_000, 600_000, published));
+    assert_eq!(
+        suite.close_round().unwrap_err(),
+        ContractError::StalePrice { published }
+    );
+
+    suite.set_price(pyth_price(120_000_000, 600_000, suite.now()));
+    suite.close_round().unwrap();
+    let round: RoundResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.game.clone(),
+            &QueryMsg::FinishedRound {
+                round_id: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    assert_eq!(round.close_price, Decimal::percent(120));
+    assert_eq!(round.winner, Some(Direction::Bull));
+    assert!(matches!(
+        round.pricing.unwrap().source,
+        PriceSource::Pyth { .. }
+    ));
+}
+
+#[test]
+fn a_price_move_after_lock_decides_the_round() {
+    let mut suite = Suite::new();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::UpdateConfig {
+                config: Box::new(ConfigUpdate {
+                    price_twap_samples: Some(5),
+                    ..ConfigUpdate::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+    /* Lock at 1.00 */
+    suite.close_round().unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.set_price(pyth_price(100_000_000, 100_000, suite.now()));
+    suite.close_round().unwrap();
+
+    /* Still 1.00 half way through */
+    suite.advance(ROUND_SECONDS / 2);
+    suite.set_price(pyth_price(100_000_000, 100_000, suite.now()));
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::CheckpointPrice {
+                price_updates: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    /* 1.20 since then, which weighs for the second half of the round */
+    suite.advance(ROUND_SECONDS / 2);
+    suite.set_price(pyth_price(120_000_000, 100_000, suite.now()));
+    suite.close_round().unwrap();
+    let round: RoundResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.game.clone(),
+            &QueryMsg::FinishedRound {
+                round_id: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    assert_eq!(round.open_price, Decimal::one());
+    assert_eq!(round.close_price, Decimal::percent(110));
+    assert_eq!(round.winner, Some(Direction::Bull));
+}
+
+#[test]
+fn price_updates_are_pushed_before_reading() {
+    let mut suite = Suite::new();
+    suite.close_round().unwrap();
+
+    /* The feed hasn't been updated since the bids opened */
+    suite.advance(ROUND_SECONDS);
+    assert!(matches!(
+        suite.close_round().unwrap_err(),
+        ContractError::StalePrice { .. }
+    ));
+
+    /* The update sent along, fee included, is in by the time the price is read */
+    let update = to_binary(&pyth_price(125_000_000, 100_000, suite.now())).unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::CloseRound {
+                price_updates: Some(vec![update]),
+            },
+            &[coin(1, "usei")],
+        )
+        .unwrap();
+    let status: StatusResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(suite.game.clone(), &QueryMsg::Status {})
+        .unwrap();
+    assert_eq!(status.live_round.unwrap().open_price, Decimal::percent(125));
+    assert_eq!(
+        suite
+            .app
+            .wrap()
+            .query_balance(suite.pyth.clone(), "usei")
+            .unwrap()
+            .amount,
+        Uint128::new(1)
+    );
+
+    /* Checkpoints too */
+    suite.advance(ROUND_SECONDS / 2);
+    let update = to_binary(&pyth_price(130_000_000, 100_000, suite.now())).unwrap();
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            suite.game.clone(),
+            &ExecuteMsg::CheckpointPrice {
+                price_updates: Some(vec![update]),
+            },
+            &[],
+        )
+        .unwrap();
+    let history: PriceHistoryResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            suite.game.clone(),
+            &QueryMsg::PriceHistory {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(history.samples.last().unwrap().price, Decimal::percent(130));
+}
+
+#[test]
+fn pyth_feed_id_must_be_hex() {
+    let err = Suite::with_config(|config| {
+        config.price_source = Some(PriceSource::Pyth {
+            contract: Addr::unchecked("pyth"),
+            feed_id: "BTC/USD".to_string(),
+            max_confidence: Decimal::percent(1),
+            max_age_seconds: 60,
+        })
+    })
+    .err()
+    .unwrap();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "a Pyth feed id is 32 bytes in hex".to_string(),
+        }
+    );
+}
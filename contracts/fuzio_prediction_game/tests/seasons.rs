@@ -0,0 +1,347 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coin, coins, Addr, Decimal, Timestamp, Uint128};
+use fuzio_bet::fuzio_prediction_game::msg::{ExecuteMsg, QueryMsg};
+use fuzio_bet::fuzio_prediction_game::{
+    Direction, LeaderboardMetric, LeaderboardPeriod, LeaderboardResponse, PlayerStatsResponse,
+    Season,
+};
+use fuzio_prediction_game::error::ContractError;
+
+/// Creates season 1 from now to `end_time` and funds it with `prize_pool`
+fn create_season(
+    suite: &mut Suite,
+    end_time: Timestamp,
+    min_bet: Option<u128>,
+    min_rounds: Option<u64>,
+    prize_curve: Vec<Decimal>,
+    prize_pool: u128,
+) {
+    let start_time = suite.now();
+    suite
+        .execute(
+            OWNER,
+            ExecuteMsg::CreateSeason {
+                start_time,
+                end_time,
+                min_bet: min_bet.map(Uint128::new),
+                min_rounds,
+                fee_share: Decimal::zero(),
+                prize_curve,
+            },
+            &[],
+        )
+        .unwrap();
+    if prize_pool > 0 {
+        suite
+            .execute(
+                OWNER,
+                ExecuteMsg::FundSeason { season_id: 1 },
+                &[coin(prize_pool, DENOM)],
+            )
+            .unwrap();
+    }
+}
+
+/// The season's ranking by net PnL, as (player, value)
+fn season_ranking(suite: &Suite) -> Vec<(String, String)> {
+    let leaderboard: LeaderboardResponse = suite.query(QueryMsg::Leaderboard {
+        metric: LeaderboardMetric::NetPnl,
+        period: LeaderboardPeriod::Season { season_id: 1 },
+        limit: None,
+        at: None,
+    });
+    leaderboard
+        .entries
+        .into_iter()
+        .map(|entry| (entry.player.to_string(), entry.value))
+        .collect()
+}
+
+#[test]
+fn losing_bets_count_when_their_round_closes() {
+    let mut suite = Suite::new();
+
+    /* The season covers the first round only */
+    let end_time = suite.now().plus_seconds(2 * ROUND_SECONDS + 100);
+    create_season(
+        &mut suite,
+        end_time,
+        None,
+        None,
+        vec![Decimal::one()],
+        1_000,
+    );
+
+    /* Alice bets up and Bob down on round 0, which goes up */
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 0, Direction::Bull, 10_000).unwrap();
+    suite.bet(BOB, 0, Direction::Bear, 10_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(100_000_000);
+
+    /* Round 0 closes within the season, which can't be finalized before */
+    suite.advance(ROUND_SECONDS + 100);
+    assert_eq!(
+        suite
+            .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+            .unwrap_err(),
+        ContractError::SeasonRoundsOpen { season_id: 1 }
+    );
+    suite.close_round_at(120_000_000);
+
+    /* Bob's loss counts without anybody settling his bet */
+    let stats: PlayerStatsResponse = suite.query(QueryMsg::PlayerStats {
+        player: Addr::unchecked(BOB),
+    });
+    assert_eq!(stats.stats.rounds_lost, 1);
+    assert_eq!(stats.net_pnl, "-10000");
+
+    /* Alice is owed 20000 less the 3% fee, claimed or not */
+    let leaderboard: LeaderboardResponse = suite.query(QueryMsg::Leaderboard {
+        metric: LeaderboardMetric::NetPnl,
+        period: LeaderboardPeriod::AllTime,
+        limit: None,
+        at: None,
+    });
+    let ranking = leaderboard
+        .entries
+        .into_iter()
+        .map(|entry| (entry.player.to_string(), entry.value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranking,
+        vec![
+            (ALICE.to_string(), "9400".to_string()),
+            (BOB.to_string(), "-10000".to_string()),
+        ]
+    );
+
+    /* The season is won by Alice before she claims */
+    let before = suite.balance(ALICE);
+    suite
+        .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), before + Uint128::new(1_000));
+    let season: Season = suite.query(QueryMsg::Season { season_id: 1 });
+    assert_eq!(season.winners[0].player, Addr::unchecked(ALICE));
+}
+
+#[test]
+fn small_bets_and_few_rounds_dont_qualify() {
+    let mut suite = Suite::new();
+
+    /* The season covers rounds 0 and 1, counting bets of 5000 or more from
+    players with bets in both */
+    let end_time = suite.now().plus_seconds(3 * ROUND_SECONDS + 100);
+    create_season(
+        &mut suite,
+        end_time,
+        Some(5_000),
+        Some(2),
+        vec![Decimal::one()],
+        1_000,
+    );
+
+    /* Bob makes the most but only plays round 0, Carol's bets are too small */
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 0, Direction::Bull, 5_000).unwrap();
+    suite.bet(BOB, 0, Direction::Bull, 20_000).unwrap();
+    suite.bet(CAROL, 0, Direction::Bull, 4_000).unwrap();
+    suite.bet(OWNER, 0, Direction::Bear, 30_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 1, Direction::Bull, 5_000).unwrap();
+    suite.bet(CAROL, 1, Direction::Bull, 4_000).unwrap();
+    suite.bet(OWNER, 1, Direction::Bear, 10_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(110_000_000);
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(120_000_000);
+
+    /* Round 0 pays 59000 over 29000 up, round 1 19000 over 9000 */
+    assert_eq!(
+        season_ranking(&suite),
+        vec![
+            (BOB.to_string(), "20689".to_string()),
+            (ALICE.to_string(), "10727".to_string()),
+            (OWNER.to_string(), "-40000".to_string()),
+        ]
+    );
+
+    suite.advance(100);
+    let before = suite.balance(ALICE);
+    suite
+        .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), before + Uint128::new(1_000));
+    let season: Season = suite.query(QueryMsg::Season { season_id: 1 });
+    assert_eq!(season.winners.len(), 1);
+    assert_eq!(season.winners[0].player, Addr::unchecked(ALICE));
+}
+
+#[test]
+fn prizes_follow_the_curve() {
+    let mut suite = Suite::new();
+    let end_time = suite.now().plus_seconds(2 * ROUND_SECONDS + 100);
+    create_season(
+        &mut suite,
+        end_time,
+        None,
+        None,
+        vec![Decimal::percent(70), Decimal::percent(30)],
+        1_000,
+    );
+
+    /* Alice makes 10000 and Bob 5000 out of Carol's stake */
+    suite.close_round_at(100_000_000);
+    suite.bet(ALICE, 0, Direction::Bull, 10_000).unwrap();
+    suite.bet(BOB, 0, Direction::Bull, 5_000).unwrap();
+    suite.bet(CAROL, 0, Direction::Bear, 15_000).unwrap();
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(100_000_000);
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(120_000_000);
+
+    suite.advance(100);
+    let alice = suite.balance(ALICE);
+    let bob = suite.balance(BOB);
+    let carol = suite.balance(CAROL);
+    suite
+        .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+        .unwrap();
+    assert_eq!(suite.balance(ALICE), alice + Uint128::new(700));
+    assert_eq!(suite.balance(BOB), bob + Uint128::new(300));
+    assert_eq!(suite.balance(CAROL), carol);
+
+    let season: Season = suite.query(QueryMsg::Season { season_id: 1 });
+    let prizes = season
+        .winners
+        .into_iter()
+        .map(|prize| (prize.rank, prize.player.to_string(), prize.prize.u128()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        prizes,
+        vec![(1, ALICE.to_string(), 700), (2, BOB.to_string(), 300)]
+    );
+}
+
+#[test]
+fn seasons_are_finalized_once() {
+    let mut suite = Suite::new();
+    let end_time = suite.now().plus_seconds(ROUND_SECONDS);
+    create_season(&mut suite, end_time, None, None, vec![Decimal::one()], 0);
+    suite.close_round_at(100_000_000);
+
+    /* Nobody played and nothing was funded, so there is nothing to pay */
+    suite.advance(ROUND_SECONDS);
+    let res = suite
+        .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+        .unwrap();
+    assert!(res.events.iter().all(|event| event.ty != "transfer"));
+    let season: Season = suite.query(QueryMsg::Season { season_id: 1 });
+    assert!(season.finalized);
+    assert!(season.winners.is_empty());
+
+    assert_eq!(
+        suite
+            .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+            .unwrap_err(),
+        ContractError::SeasonFinalized { season_id: 1 }
+    );
+    assert_eq!(
+        suite
+            .execute(
+                OWNER,
+                ExecuteMsg::FundSeason { season_id: 1 },
+                &[coin(1_000, DENOM)],
+            )
+            .unwrap_err(),
+        ContractError::SeasonFinalized { season_id: 1 }
+    );
+}
+
+#[test]
+fn busy_rounds_are_scored_over_several_calls() {
+    let mut suite = Suite::new();
+    let end_time = suite.now().plus_seconds(2 * ROUND_SECONDS + 100);
+    create_season(&mut suite, end_time, None, None, vec![Decimal::one()], 0);
+
+    /* 40 players bet on round 0, the even ones up and the odd ones down */
+    let players = (0..40)
+        .map(|i| format!("player{:02}", i))
+        .collect::<Vec<_>>();
+    suite.app.init_modules(|router, _, storage| {
+        for player in &players {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(player), coins(1_000, DENOM))
+                .unwrap();
+        }
+    });
+    suite.close_round_at(100_000_000);
+    for (i, player) in players.iter().enumerate() {
+        let direction = if i % 2 == 0 {
+            Direction::Bull
+        } else {
+            Direction::Bear
+        };
+        suite.bet(player, 0, direction, 1_000).unwrap();
+    }
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(100_000_000);
+    suite.advance(ROUND_SECONDS);
+    suite.close_round_at(120_000_000);
+
+    /* Closing scored the first 30, the season waits for the rest */
+    let rounds_won = |suite: &Suite, player: &str| {
+        let stats: PlayerStatsResponse = suite.query(QueryMsg::PlayerStats {
+            player: Addr::unchecked(player),
+        });
+        stats.stats.rounds_won
+    };
+    assert_eq!(rounds_won(&suite, "player28"), 1);
+    assert_eq!(rounds_won(&suite, "player30"), 0);
+    suite.advance(100);
+    assert_eq!(
+        suite
+            .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+            .unwrap_err(),
+        ContractError::SeasonRoundsUnscored { season_id: 1 }
+    );
+
+    /* A bet claimed before it is scored is scored once */
+    suite
+        .execute(
+            "player36",
+            ExecuteMsg::CollectWinnings { recipient: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(rounds_won(&suite, "player36"), 1);
+
+    let score = |suite: &mut Suite, limit: u32| {
+        let res = suite
+            .execute(OWNER, ExecuteMsg::ScoreRounds { limit: Some(limit) }, &[])
+            .unwrap();
+        let attribute = |key: &str| {
+            res.custom_attrs(1)
+                .iter()
+                .find(|attr| attr.key == key)
+                .unwrap()
+                .value
+                .clone()
+        };
+        (attribute("scored"), attribute("done"))
+    };
+    assert_eq!(score(&mut suite, 5), ("5".to_string(), "false".to_string()));
+    assert_eq!(rounds_won(&suite, "player34"), 1);
+    assert_eq!(score(&mut suite, 5), ("4".to_string(), "true".to_string()));
+    assert_eq!(rounds_won(&suite, "player36"), 1);
+    assert_eq!(rounds_won(&suite, "player38"), 1);
+
+    suite
+        .execute(OWNER, ExecuteMsg::FinalizeSeason { season_id: 1 }, &[])
+        .unwrap();
+}
@@ -0,0 +1,69 @@
+use cosmwasm_std::{Addr, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/*
+ * The part of an Astroport-like AMM pair's interface the game reads. The
+ * responses aren't `cw_serde` so fields pairs add don't break parsing.
+ */
+
+/* The price accumulator counts seconds times the price with 6 decimals */
+pub const TWAP_PRECISION: u128 = 1_000_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Pool {},
+    Simulation {
+        offer_asset: Asset,
+        ask_asset_info: Option<AssetInfo>,
+    },
+    CumulativePrices {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Token { contract_addr: Addr },
+    NativeToken { denom: String },
+}
+
+impl AssetInfo {
+    /// Whether this is the native denom or cw20 contract address
+    pub fn is(&self, denom: &str) -> bool {
+        match self {
+            AssetInfo::Token { contract_addr } => contract_addr.as_str() == denom,
+            AssetInfo::NativeToken { denom: native } => native == denom,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+/**
+ * Each accumulator is of the price of the first asset in the second one,
+ * wrapping around on overflow
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CumulativePricesResponse {
+    pub assets: Vec<Asset>,
+    pub total_share: Uint128,
+    pub cumulative_prices: Vec<(AssetInfo, AssetInfo, Uint128)>,
+}
@@ -0,0 +1,613 @@
+//! Typed events emitted by the prediction game. Each one serializes to a wasm
+//! event and can be parsed back from the events of a transaction.
+
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    attr, from_slice, to_vec, Addr, Attribute, Decimal, Event, StdError, StdResult, Timestamp,
+    Uint128,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::fuzio_prediction_game::{
+    ConfigUpdate, Direction, PricingMethod, SourcePrice, WalletInfo,
+};
+
+/* wasmd prefixes the type of the events emitted by contracts */
+const WASM_EVENT_PREFIX: &str = "wasm-";
+
+pub trait TypedEvent: Sized {
+    const TYPE: &'static str;
+
+    fn to_event(&self) -> Event;
+
+    fn from_event(event: &Event) -> StdResult<Self>;
+
+    /**
+     * Parses the event if it is of this type, whether it comes straight from
+     * a response or from the transaction logs
+     */
+    fn parse(event: &Event) -> StdResult<Option<Self>> {
+        let ty = event
+            .ty
+            .strip_prefix(WASM_EVENT_PREFIX)
+            .unwrap_or(&event.ty);
+        if ty != Self::TYPE {
+            return Ok(None);
+        }
+        Self::from_event(event).map(Some)
+    }
+}
+
+/// A bet, with the round's pools once it is added
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetPlaced {
+    pub round_id: Uint128,
+    pub player: Addr,
+    pub direction: Direction,
+    pub amount: Uint128,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+}
+
+impl TypedEvent for BetPlaced {
+    const TYPE: &'static str = "fuzio_bet_placed";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("round_id", self.round_id)
+            .add_attribute("player", &self.player)
+            .add_attribute("direction", self.direction.to_string())
+            .add_attribute("amount", self.amount)
+            .add_attribute("bull_amount", self.bull_amount)
+            .add_attribute("bear_amount", self.bear_amount)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(BetPlaced {
+            round_id: parse_attribute(event, "round_id")?,
+            player: Addr::unchecked(attribute(event, "player")?),
+            direction: parse_attribute(event, "direction")?,
+            amount: parse_attribute(event, "amount")?,
+            bull_amount: parse_attribute(event, "bull_amount")?,
+            bear_amount: parse_attribute(event, "bear_amount")?,
+        })
+    }
+}
+
+/// A round stopped taking bets and went live at its open price
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundLocked {
+    pub round_id: Uint128,
+    pub open_price: Decimal,
+    pub close_time: Timestamp,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+}
+
+impl TypedEvent for RoundLocked {
+    const TYPE: &'static str = "fuzio_round_locked";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("round_id", self.round_id)
+            .add_attribute("open_price", self.open_price.to_string())
+            .add_attribute("close_time", self.close_time.seconds().to_string())
+            .add_attribute("bull_amount", self.bull_amount)
+            .add_attribute("bear_amount", self.bear_amount)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(RoundLocked {
+            round_id: parse_attribute(event, "round_id")?,
+            open_price: parse_attribute(event, "open_price")?,
+            close_time: Timestamp::from_seconds(parse_attribute(event, "close_time")?),
+            bull_amount: parse_attribute(event, "bull_amount")?,
+            bear_amount: parse_attribute(event, "bear_amount")?,
+        })
+    }
+}
+
+/// A live round closed. There is no winner when the price didn't move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundSettled {
+    pub round_id: Uint128,
+    pub open_price: Decimal,
+    pub close_price: Decimal,
+    pub winner: Option<Direction>,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    pub pricing: Option<PricingMethod>,
+    /* What each source returned when the price is a median */
+    pub source_prices: Vec<SourcePrice>,
+    pub voided: bool,
+}
+
+impl TypedEvent for RoundSettled {
+    const TYPE: &'static str = "fuzio_round_settled";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::TYPE)
+            .add_attribute("round_id", self.round_id)
+            .add_attribute("open_price", self.open_price.to_string())
+            .add_attribute("close_price", self.close_price.to_string())
+            .add_attribute("bull_amount", self.bull_amount)
+            .add_attribute("bear_amount", self.bear_amount);
+        let event = match &self.pricing {
+            Some(pricing) => event.add_attribute("pricing", json_value(pricing)),
+            None => event,
+        };
+        let event = if self.source_prices.is_empty() {
+            event
+        } else {
+            event.add_attribute("source_prices", json_value(&self.source_prices))
+        };
+        let event = if self.voided {
+            event.add_attribute("voided", "true")
+        } else {
+            event
+        };
+        match &self.winner {
+            Some(winner) => event.add_attribute("winner", winner.to_string()),
+            None => event,
+        }
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(RoundSettled {
+            round_id: parse_attribute(event, "round_id")?,
+            open_price: parse_attribute(event, "open_price")?,
+            close_price: parse_attribute(event, "close_price")?,
+            winner: may_attribute(event, "winner")
+                .map(Direction::from_str)
+                .transpose()?,
+            bull_amount: parse_attribute(event, "bull_amount")?,
+            bear_amount: parse_attribute(event, "bear_amount")?,
+            pricing: may_attribute(event, "pricing")
+                .map(parse_json_value)
+                .transpose()?,
+            source_prices: may_attribute(event, "source_prices")
+                .map(parse_json_value)
+                .transpose()?
+                .unwrap_or_default(),
+            voided: may_parse_attribute(event, "voided")?.unwrap_or_default(),
+        })
+    }
+}
+
+/// Winnings paid out, net of the gaming fee
+#[derive(Clone, Debug, PartialEq)]
+pub struct Claimed {
+    pub player: Addr,
+    pub claimed_by: Addr,
+    pub recipient: Addr,
+    pub amount: Uint128,
+}
+
+impl TypedEvent for Claimed {
+    const TYPE: &'static str = "fuzio_claimed";
+
+    fn to_event(&self) -> Event {
+        Event::new(Self::TYPE)
+            .add_attribute("player", &self.player)
+            .add_attribute("claimed_by", &self.claimed_by)
+            .add_attribute("recipient", &self.recipient)
+            .add_attribute("amount", self.amount)
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(Claimed {
+            player: Addr::unchecked(attribute(event, "player")?),
+            claimed_by: Addr::unchecked(attribute(event, "claimed_by")?),
+            recipient: Addr::unchecked(attribute(event, "recipient")?),
+            amount: parse_attribute(event, "amount")?,
+        })
+    }
+}
+
+/// The gaming fee taken on a claim, split between the dev wallets and the
+/// prize pool of the season running at the time
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeesDistributed {
+    pub amount: Uint128,
+    pub denom: String,
+    /* The season whose prize pool got `season_amount` of the fee */
+    pub season_id: Option<u64>,
+    pub season_amount: Uint128,
+}
+
+impl TypedEvent for FeesDistributed {
+    const TYPE: &'static str = "fuzio_fees_distributed";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::TYPE)
+            .add_attribute("amount", self.amount)
+            .add_attribute("denom", &self.denom);
+        match self.season_id {
+            Some(season_id) => event
+                .add_attribute("season_id", season_id.to_string())
+                .add_attribute("season_amount", self.season_amount),
+            None => event,
+        }
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(FeesDistributed {
+            amount: parse_attribute(event, "amount")?,
+            denom: attribute(event, "denom")?.to_string(),
+            season_id: may_parse_attribute(event, "season_id")?,
+            season_amount: may_parse_attribute(event, "season_amount")?.unwrap_or_default(),
+        })
+    }
+}
+
+/// Config fields changed, with the queued change it came from if any
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigChanged {
+    pub id: Option<u64>,
+    pub changes: ConfigUpdate,
+}
+
+impl TypedEvent for ConfigChanged {
+    const TYPE: &'static str = "fuzio_config_changed";
+
+    fn to_event(&self) -> Event {
+        let event = Event::new(Self::TYPE).add_attributes(config_update_attributes(&self.changes));
+        match self.id {
+            Some(id) => event.add_attribute("id", id.to_string()),
+            None => event,
+        }
+    }
+
+    fn from_event(event: &Event) -> StdResult<Self> {
+        Ok(ConfigChanged {
+            id: may_parse_attribute(event, "id")?,
+            changes: ConfigUpdate {
+                next_round_seconds: may_parse_attribute(event, "next_round_seconds")?,
+                minimum_bet: may_parse_attribute(event, "minimum_bet")?,
+                gaming_fee: may_parse_attribute(event, "gaming_fee")?,
+                token_denom: may_attribute(event, "token_denom").map(str::to_string),
+                bet_token_denom: may_attribute(event, "bet_token_denom").map(str::to_string),
+                dev_wallet_list: may_attribute(event, "dev_wallet_list")
+                    .map(parse_dev_wallets)
+                    .transpose()?,
+                claim_expiry_seconds: may_parse_attribute(event, "claim_expiry_seconds")?,
+                treasury: may_attribute(event, "treasury").map(Addr::unchecked),
+                config_timelock_seconds: may_parse_attribute(event, "config_timelock_seconds")?,
+                price_twap_samples: may_parse_attribute(event, "price_twap_samples")?,
+                price_source: may_attribute(event, "price_source")
+                    .map(parse_json_value)
+                    .transpose()?,
+                clear: may_attribute(event, "clear")
+                    .map(parse_json_value)
+                    .transpose()?,
+            },
+        })
+    }
+}
+
+/// Any of the events above
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    BetPlaced(BetPlaced),
+    RoundLocked(RoundLocked),
+    RoundSettled(RoundSettled),
+    Claimed(Claimed),
+    FeesDistributed(FeesDistributed),
+    ConfigChanged(ConfigChanged),
+}
+
+impl GameEvent {
+    /// Parses a game event, `None` for events of other types
+    pub fn parse(event: &Event) -> StdResult<Option<Self>> {
+        if let Some(e) = BetPlaced::parse(event)? {
+            return Ok(Some(GameEvent::BetPlaced(e)));
+        }
+        if let Some(e) = RoundLocked::parse(event)? {
+            return Ok(Some(GameEvent::RoundLocked(e)));
+        }
+        if let Some(e) = RoundSettled::parse(event)? {
+            return Ok(Some(GameEvent::RoundSettled(e)));
+        }
+        if let Some(e) = Claimed::parse(event)? {
+            return Ok(Some(GameEvent::Claimed(e)));
+        }
+        if let Some(e) = FeesDistributed::parse(event)? {
+            return Ok(Some(GameEvent::FeesDistributed(e)));
+        }
+        Ok(ConfigChanged::parse(event)?.map(GameEvent::ConfigChanged))
+    }
+
+    /// The game events among the events of a transaction, in order
+    pub fn parse_all(events: &[Event]) -> StdResult<Vec<Self>> {
+        events
+            .iter()
+            .filter_map(|event| Self::parse(event).transpose())
+            .collect()
+    }
+}
+
+/// One attribute per field set in the update
+pub fn config_update_attributes(update: &ConfigUpdate) -> Vec<Attribute> {
+    let mut attributes = vec![];
+
+    if let Some(next_round_seconds) = update.next_round_seconds {
+        attributes.push(attr("next_round_seconds", next_round_seconds));
+    }
+    if let Some(minimum_bet) = update.minimum_bet {
+        attributes.push(attr("minimum_bet", minimum_bet));
+    }
+    if let Some(gaming_fee) = update.gaming_fee {
+        attributes.push(attr("gaming_fee", gaming_fee));
+    }
+    if let Some(token_denom) = &update.token_denom {
+        attributes.push(attr("token_denom", token_denom));
+    }
+    if let Some(bet_token_denom) = &update.bet_token_denom {
+        attributes.push(attr("bet_token_denom", bet_token_denom));
+    }
+    if let Some(dev_wallet_list) = &update.dev_wallet_list {
+        let wallets = dev_wallet_list
+            .iter()
+            .map(|wallet| format!("{}:{}", wallet.address, wallet.ratio))
+            .collect::<Vec<_>>()
+            .join(",");
+        attributes.push(attr("dev_wallet_list", wallets));
+    }
+    if let Some(claim_expiry_seconds) = update.claim_expiry_seconds {
+        attributes.push(attr("claim_expiry_seconds", claim_expiry_seconds));
+    }
+    if let Some(treasury) = &update.treasury {
+        attributes.push(attr("treasury", treasury));
+    }
+    if let Some(config_timelock_seconds) = update.config_timelock_seconds {
+        attributes.push(attr("config_timelock_seconds", config_timelock_seconds));
+    }
+    if let Some(price_twap_samples) = update.price_twap_samples {
+        attributes.push(attr("price_twap_samples", price_twap_samples.to_string()));
+    }
+    if let Some(price_source) = &update.price_source {
+        attributes.push(attr("price_source", json_value(price_source)));
+    }
+    if let Some(clear) = &update.clear {
+        attributes.push(attr("clear", json_value(clear)));
+    }
+
+    attributes
+}
+
+/* Structured values are written as JSON */
+fn json_value<T: Serialize>(value: &T) -> String {
+    to_vec(value)
+        .map(|json| String::from_utf8_lossy(&json).into_owned())
+        .unwrap_or_default()
+}
+
+fn parse_json_value<T: DeserializeOwned>(value: &str) -> StdResult<T> {
+    from_slice(value.as_bytes())
+}
+
+fn parse_dev_wallets(wallets: &str) -> StdResult<Vec<WalletInfo>> {
+    if wallets.is_empty() {
+        return Ok(vec![]);
+    }
+    wallets
+        .split(',')
+        .map(|wallet| {
+            let (address, ratio) = wallet.split_once(':').ok_or_else(|| {
+                StdError::parse_err("WalletInfo", format!("Invalid dev wallet {}", wallet))
+            })?;
+            Ok(WalletInfo {
+                address: Addr::unchecked(address),
+                ratio: Decimal::from_str(ratio)?,
+            })
+        })
+        .collect()
+}
+
+fn may_attribute<'a>(event: &'a Event, key: &str) -> Option<&'a str> {
+    event
+        .attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .map(|attribute| attribute.value.as_str())
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> StdResult<&'a str> {
+    may_attribute(event, key).ok_or_else(|| {
+        StdError::generic_err(format!("Missing attribute {} in {} event", key, event.ty))
+    })
+}
+
+fn may_parse_attribute<T>(event: &Event, key: &str) -> StdResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: ToString,
+{
+    may_attribute(event, key)
+        .map(|value| {
+            value.parse().map_err(|e: T::Err| {
+                StdError::parse_err(key, format!("{} in {} event", e.to_string(), event.ty))
+            })
+        })
+        .transpose()
+}
+
+fn parse_attribute<T>(event: &Event, key: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: ToString,
+{
+    may_parse_attribute(event, key)?.ok_or_else(|| {
+        StdError::generic_err(format!("Missing attribute {} in {} event", key, event.ty))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzio_prediction_game::{OptionalConfigField, PriceSource};
+
+    /* Emits the event the way wasmd logs it and parses it back */
+    fn round_trip(event: GameEvent) {
+        let mut emitted = match &event {
+            GameEvent::BetPlaced(e) => e.to_event(),
+            GameEvent::RoundLocked(e) => e.to_event(),
+            GameEvent::RoundSettled(e) => e.to_event(),
+            GameEvent::Claimed(e) => e.to_event(),
+            GameEvent::FeesDistributed(e) => e.to_event(),
+            GameEvent::ConfigChanged(e) => e.to_event(),
+        };
+        emitted.ty = format!("{}{}", WASM_EVENT_PREFIX, emitted.ty);
+        let other = Event::new("transfer").add_attribute("amount", "1usei");
+
+        assert_eq!(
+            GameEvent::parse_all(&[other, emitted]).unwrap(),
+            vec![event]
+        );
+    }
+
+    #[test]
+    fn bet_placed_round_trip() {
+        round_trip(GameEvent::BetPlaced(BetPlaced {
+            round_id: Uint128::new(7),
+            player: Addr::unchecked("player"),
+            direction: Direction::Bear,
+            amount: Uint128::new(1_000),
+            bull_amount: Uint128::new(5_000),
+            bear_amount: Uint128::new(3_000),
+        }));
+    }
+
+    #[test]
+    fn round_locked_round_trip() {
+        round_trip(GameEvent::RoundLocked(RoundLocked {
+            round_id: Uint128::new(7),
+            open_price: Decimal::percent(125),
+            close_time: Timestamp::from_seconds(1_700_000_600),
+            bull_amount: Uint128::new(5_000),
+            bear_amount: Uint128::new(3_000),
+        }));
+    }
+
+    #[test]
+    fn round_settled_round_trip() {
+        round_trip(GameEvent::RoundSettled(RoundSettled {
+            round_id: Uint128::new(7),
+            open_price: Decimal::percent(125),
+            close_price: Decimal::percent(130),
+            winner: Some(Direction::Bull),
+            bull_amount: Uint128::new(5_000),
+            bear_amount: Uint128::new(3_000),
+            pricing: Some(PricingMethod {
+                source: PriceSource::Median {
+                    sources: vec![
+                        PriceSource::SeiOracle {},
+                        PriceSource::SeiOracleTwap {
+                            lookback_seconds: 600,
+                        },
+                    ],
+                    quorum: 1,
+                    max_age_seconds: None,
+                },
+                twap_samples: Some(3),
+            }),
+            source_prices: vec![
+                SourcePrice {
+                    source: PriceSource::SeiOracle {},
+                    price: Some(Decimal::percent(130)),
+                },
+                SourcePrice {
+                    source: PriceSource::SeiOracleTwap {
+                        lookback_seconds: 600,
+                    },
+                    price: None,
+                },
+            ],
+            voided: false,
+        }));
+
+        /* Without a winner nor pricing, voided */
+        round_trip(GameEvent::RoundSettled(RoundSettled {
+            round_id: Uint128::new(8),
+            open_price: Decimal::percent(125),
+            close_price: Decimal::percent(125),
+            winner: None,
+            bull_amount: Uint128::zero(),
+            bear_amount: Uint128::new(3_000),
+            pricing: None,
+            source_prices: vec![],
+            voided: true,
+        }));
+    }
+
+    #[test]
+    fn claimed_round_trip() {
+        round_trip(GameEvent::Claimed(Claimed {
+            player: Addr::unchecked("player"),
+            claimed_by: Addr::unchecked("keeper"),
+            recipient: Addr::unchecked("recipient"),
+            amount: Uint128::new(7_760),
+        }));
+    }
+
+    #[test]
+    fn fees_distributed_round_trip() {
+        round_trip(GameEvent::FeesDistributed(FeesDistributed {
+            amount: Uint128::new(240),
+            denom: "usei".to_string(),
+            season_id: Some(2),
+            season_amount: Uint128::new(24),
+        }));
+        round_trip(GameEvent::FeesDistributed(FeesDistributed {
+            amount: Uint128::new(240),
+            denom: "usei".to_string(),
+            season_id: None,
+            season_amount: Uint128::zero(),
+        }));
+    }
+
+    #[test]
+    fn config_changed_round_trip() {
+        round_trip(GameEvent::ConfigChanged(ConfigChanged {
+            id: Some(3),
+            changes: ConfigUpdate {
+                next_round_seconds: Some(Uint128::new(300)),
+                minimum_bet: Some(Uint128::new(1_000)),
+                gaming_fee: Some(Uint128::new(300)),
+                token_denom: Some("usei".to_string()),
+                bet_token_denom: Some("uatom".to_string()),
+                dev_wallet_list: Some(vec![
+                    WalletInfo {
+                        address: Addr::unchecked("dev1"),
+                        ratio: Decimal::percent(60),
+                    },
+                    WalletInfo {
+                        address: Addr::unchecked("dev2"),
+                        ratio: Decimal::percent(40),
+                    },
+                ]),
+                claim_expiry_seconds: Some(Uint128::new(7_776_000)),
+                treasury: Some(Addr::unchecked("treasury")),
+                config_timelock_seconds: Some(Uint128::new(86_400)),
+                price_twap_samples: Some(5),
+                price_source: Some(PriceSource::Feeders {
+                    quorum: 2,
+                    max_age_seconds: 60,
+                    tolerance: Some(Decimal::percent(2)),
+                }),
+                clear: Some(vec![OptionalConfigField::PriceTwapSamples]),
+            },
+        }));
+
+        /* Applied right away, changing a single field */
+        round_trip(GameEvent::ConfigChanged(ConfigChanged {
+            id: None,
+            changes: ConfigUpdate {
+                gaming_fee: Some(Uint128::new(200)),
+                ..ConfigUpdate::default()
+            },
+        }));
+    }
+}
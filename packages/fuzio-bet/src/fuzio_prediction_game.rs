@@ -1,8 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdError, StdResult, Timestamp, Uint128, WasmMsg};
 
 pub const FEE_PRECISION: u128 = 100u128;
+/* The gaming fee can't be set above 10% */
+pub const MAX_GAMING_FEE: u128 = 10 * FEE_PRECISION;
 
 #[cw_serde]
 pub enum Direction {
@@ -10,19 +12,62 @@ pub enum Direction {
     Bear,
 }
 
-impl ToString for Direction {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Direction::Bull => "bull",
-            Direction::Bear => "bear",
+            Direction::Bull => write!(f, "bull"),
+            Direction::Bear => write!(f, "bear"),
+        }
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = StdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bull" => Ok(Direction::Bull),
+            "bear" => Ok(Direction::Bear),
+            _ => Err(StdError::parse_err("Direction", format!("Unknown direction {}", s))),
+        }
+    }
+}
+
+/**
+ * What an address is allowed to do. Owners can do everything.
+ */
+#[cw_serde]
+pub enum Role {
+    Owner,
+    /* Update the config, except where the fees go */
+    ConfigManager,
+    /* Halt and resume the game */
+    Pauser,
+    /* Change where the fees go, sweep expired winnings and prune history */
+    FeeManager,
+    /* Push prices */
+    OracleFeeder,
+    /* Create markets and seasons */
+    MarketCreator,
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Owner => write!(f, "owner"),
+            Role::ConfigManager => write!(f, "config_manager"),
+            Role::Pauser => write!(f, "pauser"),
+            Role::FeeManager => write!(f, "fee_manager"),
+            Role::OracleFeeder => write!(f, "oracle_feeder"),
+            Role::MarketCreator => write!(f, "market_creator"),
         }
-        .to_string()
     }
 }
 
 #[cw_serde]
 /**
- * Parameters which are mutable by a governance vote
+ * Parameters which are mutable by a governance vote, once a DAO or multisig
+ * contract has been handed ownership with `HandOverToGovernance`
  */
 pub struct Config {
     /* After a round ends this is the duration of the next */
@@ -34,6 +79,141 @@ pub struct Config {
     //What are we betting against
     pub bet_token_denom: String,
     pub dev_wallet_list: Vec<WalletInfo>,
+    /* Winnings not claimed this long after the round closed can be swept */
+    pub claim_expiry_seconds: Option<Uint128>,
+    /* Where expired winnings are swept to */
+    pub treasury: Option<Addr>,
+    /* How long config changes wait in the queue before they can be applied */
+    pub config_timelock_seconds: Option<Uint128>,
+    /* Rounds open at the time weighted average of the prices recorded since bets opened and close at the one since they locked, reading at most this many samples, the current price included */
+    pub price_twap_samples: Option<u32>,
+    /* Where the price of the bet token is read, the Sei oracle's exchange rate by default */
+    pub price_source: Option<PriceSource>,
+}
+
+/**
+ * Where the price of the bet token comes from
+ */
+#[cw_serde]
+pub enum PriceSource {
+    /* The current exchange rate in the Sei oracle */
+    SeiOracle {},
+    /* The Sei oracle's time weighted average over the lookback window, which
+    a single block can't move much */
+    SeiOracleTwap { lookback_seconds: u64 },
+    /* Prices pushed by the oracle feeders with `PushPrice`. Pushes older than
+    `max_age_seconds` are ignored and at least `quorum` feeders have to be
+    within `tolerance` of the median of the pushes, whose median is then used.
+    Rounds that can't get a quorum are voided. */
+    Feeders {
+        quorum: u32,
+        max_age_seconds: u64,
+        tolerance: Option<Decimal>,
+    },
+    /* A Pyth price feed contract's price for `feed_id`. Prices published more
+    than `max_age_seconds` ago, or whose confidence interval is wider than
+    `max_confidence` of the price, are refused. */
+    Pyth {
+        contract: Addr,
+        feed_id: String,
+        max_confidence: Decimal,
+        max_age_seconds: u64,
+    },
+    /* An AMM pair's price of the bet token in the pair's other asset, whose
+    reserve has to be at least `min_liquidity`. With `twap_window_seconds` the
+    pair's price accumulator is averaged since the newest observation at
+    least that old, taken as rounds close, and has no price until there is one. */
+    AmmPair {
+        contract: Addr,
+        pool_type: PoolType,
+        min_liquidity: Uint128,
+        twap_window_seconds: Option<u64>,
+    },
+    /* The median of the prices the sources return, when at least `quorum`
+    of them answer with a price at most `max_age_seconds` old. Rounds that
+    can't get a quorum are voided. */
+    Median {
+        sources: Vec<PriceSource>,
+        quorum: u32,
+        max_age_seconds: Option<u64>,
+    },
+}
+
+/**
+ * How an AMM pair prices its assets
+ */
+#[cw_serde]
+pub enum PoolType {
+    /* Constant product, the price is the ratio of the reserves */
+    Xyk,
+    /* Stableswap, the price is read by simulating a small swap */
+    Stable,
+}
+
+/**
+ * What a source of a median returned, nothing if it failed or was stale
+ */
+#[cw_serde]
+pub struct SourcePrice {
+    pub source: PriceSource,
+    pub price: Option<Decimal>,
+}
+
+/**
+ * How a round's close price was taken
+ */
+#[cw_serde]
+pub struct PricingMethod {
+    pub source: PriceSource,
+    /* Samples of the price history averaged, if any */
+    pub twap_samples: Option<u32>,
+}
+
+/**
+ * Config params to change, the others are left as they are
+ */
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub next_round_seconds: Option<Uint128>,
+    pub minimum_bet: Option<Uint128>,
+    pub gaming_fee: Option<Uint128>,
+    pub token_denom: Option<String>,
+    pub bet_token_denom: Option<String>,
+    pub dev_wallet_list: Option<Vec<WalletInfo>>,
+    pub claim_expiry_seconds: Option<Uint128>,
+    pub treasury: Option<Addr>,
+    pub config_timelock_seconds: Option<Uint128>,
+    pub price_twap_samples: Option<u32>,
+    pub price_source: Option<PriceSource>,
+    /* Optional fields to unset, done before the values above are set */
+    pub clear: Option<Vec<OptionalConfigField>>,
+}
+
+/**
+ * The config fields that can be left unset
+ */
+#[cw_serde]
+pub enum OptionalConfigField {
+    ClaimExpirySeconds,
+    Treasury,
+    ConfigTimelockSeconds,
+    PriceTwapSamples,
+    PriceSource,
+}
+
+/**
+ * What is currently paused. Each can be paused on its own.
+ */
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseState {
+    /* Placing bets */
+    pub betting: bool,
+    /* Closing rounds and opening new ones */
+    pub rounds: bool,
+    /* Collecting winnings */
+    pub claims: bool,
 }
 
 #[cw_serde]
@@ -68,13 +248,57 @@ pub struct FinishedRound {
     pub winner: Option<Direction>,
     pub bull_amount: Uint128,
     pub bear_amount: Uint128,
+    /* Missing for the rounds settled before 1.1.0 and pruned rounds */
+    pub pricing: Option<PricingMethod>,
+    /* The round couldn't be priced, every bet is refunded free of fees */
+    #[serde(default)]
+    pub voided: bool,
+}
+
+/**
+ * A price read from the oracle, when a round opened or closed or at a
+ * checkpoint. Reads within the same block make a single sample.
+ */
+#[cw_serde]
+pub struct PriceSample {
+    pub time: Timestamp,
+    pub price: Decimal,
+    pub opened_round: Option<Uint128>,
+    pub closed_round: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct FeederPrice {
+    pub feeder: Addr,
+    pub price: Decimal,
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct FeederPricesResponse {
+    pub market: String,
+    pub prices: Vec<FeederPrice>,
+}
+
+#[cw_serde]
+pub struct PriceHistoryResponse {
+    pub samples: Vec<PriceSample>,
+}
+
+#[cw_serde]
+pub enum OrderBy {
+    Asc,
+    Desc,
 }
 
 pub mod msg {
     use super::*;
 
     #[cw_serde]
-    pub struct MigrateMsg {}
+    pub struct MigrateMsg {
+        /* Entries a paginated migration goes through, migrate again with the same code to carry on */
+        pub limit: Option<u32>,
+    }
 
     #[cw_serde]
     pub struct InstantiateMsg {
@@ -85,10 +309,21 @@ pub mod msg {
     #[cw_serde]
     pub enum ExecuteMsg {
         /**
-         * Update part of or all of the mutable config params
+         * Update part of or all of the mutable config params. The change is
+         * queued until the config timelock has passed.
          */
         UpdateConfig {
-            config: Config,
+            config: Box<ConfigUpdate>,
+        },
+        /**
+         * Permissionless msg to apply a queued config change once its
+         * timelock has passed
+         */
+        ExecuteConfigChange {
+            id: u64,
+        },
+        CancelConfigChange {
+            id: u64,
         },
         /**
          * Price go up
@@ -110,25 +345,197 @@ pub mod msg {
          * Permissionless msg to close the current round and open the next
          * NOTE It is permissionless because we can check timestamps :)
          */
-        CloseRound {},
+        CloseRound {
+            /* Pyth price updates to push before reading the price, the update
+            fee being the funds sent */
+            price_updates: Option<Vec<Binary>>,
+        },
+        /**
+         * Record the current price in the price history, between the round
+         * opens and closes
+         */
+        CheckpointPrice {
+            /* Pyth price updates to push before reading the price, the update
+            fee being the funds sent */
+            price_updates: Option<Vec<Binary>>,
+        },
+        /**
+         * Push the price of a market as an oracle feeder, for markets priced
+         * by their feeders. `market` is the bet token denom and `timestamp`
+         * when the price was observed.
+         */
+        PushPrice {
+            market: String,
+            price: Decimal,
+            timestamp: Timestamp,
+        },
         /**
          * Settle winnings for an account
          */
-        CollectWinnings {},
+        CollectWinnings {
+            /* Where to send the winnings, defaults to the sender */
+            recipient: Option<Addr>,
+        },
         CollectionWinningRound {
             round_id: Uint128,
+            /* Where to send the winnings, defaults to the sender */
+            recipient: Option<Addr>,
         },
+        /**
+         * Settle winnings on behalf of a player, paid to the player.
+         * Anybody can send it, e.g. a relayer.
+         */
+        CollectWinningsFor {
+            player: Addr,
+        },
+        /**
+         * Settle winnings for an account and bet all or part of them on the
+         * round open for bids. The remainder, if any, is sent back.
+         */
+        ClaimAndBet {
+            direction: Direction,
+            /* Defaults to the full amount claimed, net of fees */
+            amount: Option<Uint128>,
+        },
+        /**
+         * Move the unclaimed winnings of expired rounds to the treasury. The
+         * bets swept are deleted, at most `limit` of them per call, so
+         * sweeping again carries on with the rest.
+         */
+        SweepExpired {
+            round_ids: Vec<Uint128>,
+            limit: Option<u32>,
+        },
+        /**
+         * Delete the bets and claims of fully claimed rounds before
+         * `before_round`, keeping a summary of each round. At most `limit`
+         * rounds are pruned per call, starting after `start_after`, and the
+         * rounds, bets and claims looked at are bounded.
+         */
+        PruneHistory {
+            before_round: Uint128,
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+        },
+        /**
+         * Score the bets of the rounds that closed for the player stats,
+         * leaderboards and seasons, at most `limit` of them per call. Closing
+         * a round scores a few, anybody can carry on with the rest.
+         */
+        ScoreRounds {
+            limit: Option<u32>,
+        },
+        /**
+         * Freeze the contract for good: no more bets or rounds. The rounds
+         * open for bets and live are voided and their bettors can withdraw
+         * their stake. Winnings of finished rounds can still be collected.
+         * Only a migration can undo it.
+         */
+        EmergencyShutdown {},
+        /**
+         * Get back the sender's stakes in the rounds voided by the
+         * emergency shutdown
+         */
+        EmergencyWithdraw {},
+        /**
+         * Pause betting and rounds, claims stay open
+         */
         Halt {},
+        /**
+         * Unpause betting and rounds
+         */
         Resume {},
+        /**
+         * Pause or unpause betting, rounds and claims separately. Those left
+         * out stay as they are.
+         */
+        SetPause {
+            betting: Option<bool>,
+            rounds: Option<bool>,
+            claims: Option<bool>,
+        },
+        /**
+         * Propose to grant the owner role. It takes effect once the new
+         * admin accepts it.
+         */
         AddAdmin {
             new_admin: Addr,
+            /* Seconds the proposal stays valid, defaults to a week */
+            expires_in: Option<u64>,
+        },
+        /**
+         * Propose to hand the sender's owner role over to another address.
+         * It takes effect once the new owner accepts it.
+         */
+        TransferOwnership {
+            new_owner: Addr,
+            /* Seconds the proposal stays valid, defaults to a week */
+            expires_in: Option<u64>,
+        },
+        /**
+         * Accept the ownership proposal made to the sender
+         */
+        AcceptOwnership {},
+        /**
+         * Propose to make a DAO or multisig contract the only owner. It
+         * accepts by executing `AcceptOwnership` through a proposal, after
+         * which every other owner loses the owner role.
+         */
+        HandOverToGovernance {
+            governance: Addr,
+            /* Seconds the proposal stays valid, defaults to a week */
+            expires_in: Option<u64>,
+        },
+        CancelOwnershipProposal {
+            candidate: Addr,
         },
+        /**
+         * Revoke the owner role
+         */
         RemoveAdmin {
             old_admin: Addr,
         },
+        GrantRole {
+            address: Addr,
+            role: Role,
+        },
+        RevokeRole {
+            address: Addr,
+            role: Role,
+        },
         ModifyDevWallet {
             new_dev_wallets: Vec<WalletInfo>,
         },
+        /**
+         * Start a season over the rounds closing between `start_time` and
+         * `end_time`. Seasons can't overlap.
+         */
+        CreateSeason {
+            start_time: Timestamp,
+            end_time: Timestamp,
+            /* Bets below this don't count towards the season */
+            min_bet: Option<Uint128>,
+            /* Bets a player needs in the season's closed rounds to win a prize */
+            min_rounds: Option<u64>,
+            /* Part of the gaming fee taken during the season that goes to its prize pool */
+            fee_share: Decimal,
+            /* Share of the prize pool for each rank from the first, adding up to 1 */
+            prize_curve: Vec<Decimal>,
+        },
+        /**
+         * Add the funds sent to the prize pool of a season that hasn't been
+         * finalized yet
+         */
+        FundSeason {
+            season_id: u64,
+        },
+        /**
+         * Pay out the prize pool of a season that has ended to its top
+         * players
+         */
+        FinalizeSeason {
+            season_id: u64,
+        },
     }
 
     #[cw_serde]
@@ -138,10 +545,43 @@ pub mod msg {
         Config {},
         #[returns(StatusResponse)]
         Status {},
+        /* The last price each feeder pushed for the market, the bet token denom by default */
+        #[returns(FeederPricesResponse)]
+        FeederPrices { market: Option<String> },
+        /* Recorded prices, oldest first */
+        #[returns(PriceHistoryResponse)]
+        PriceHistory {
+            start_after: Option<Timestamp>,
+            limit: Option<u32>,
+        },
+        /* Payout multipliers of the bidding round after the gaming fee */
+        #[returns(OddsResponse)]
+        Odds { round_id: Uint128 },
+        /* What a bet in the bidding round would pay if it wins */
+        #[returns(SimulateBetResponse)]
+        SimulateBet {
+            round_id: Uint128,
+            direction: Direction,
+            amount: Uint128,
+        },
+        #[returns(PauseStateResponse)]
+        PauseState {},
+        #[returns(EmergencyResponse)]
+        Emergency {},
         #[returns(MyCurrentPositionResponse)]
         MyCurrentPosition { address: String },
         #[returns(RoundResponse)]
         FinishedRound { round_id: Uint128 },
+        /* Finished rounds by id, oldest first unless told otherwise */
+        #[returns(RoundsResponse)]
+        Rounds {
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+            order: Option<OrderBy>,
+        },
+        /* The last finished rounds, newest first */
+        #[returns(RoundsResponse)]
+        LatestRounds { count: u32 },
         #[returns(MyGameResponse)]
         MyGameList {
             player: Addr,
@@ -176,8 +616,55 @@ pub mod msg {
         TotalSpent {
             player: Addr,
         },
+        #[returns(PlayerStatsResponse)]
+        PlayerStats { player: Addr },
+        /* Top players of the period containing `at`, the current one by default */
+        #[returns(LeaderboardResponse)]
+        Leaderboard {
+            metric: LeaderboardMetric,
+            period: LeaderboardPeriod,
+            limit: Option<u32>,
+            at: Option<Timestamp>,
+        },
+        /* Protocol totals and the buckets of the last `days` days, a week by default */
+        #[returns(GlobalStatsResponse)]
+        GlobalStats { days: Option<u32> },
+        #[returns(Season)]
+        Season { season_id: u64 },
+        #[returns(SeasonsResponse)]
+        Seasons {
+            start_after: Option<u64>,
+            limit: Option<u32>,
+        },
         #[returns(AdminsResponse)]
         GetAdmins {},
+        #[returns(RolesResponse)]
+        Roles { address: Addr },
+        #[returns(PendingConfigChangesResponse)]
+        PendingConfigChanges {},
+        /* Pending proposals, all of them if no candidate is given */
+        #[returns(OwnershipProposalResponse)]
+        OwnershipProposal { candidate: Option<Addr> },
+        #[returns(ClaimExpiryResponse)]
+        ClaimExpiry { round_id: Uint128 },
+        /* The governance contract owning the contract, if any */
+        #[returns(GovernanceResponse)]
+        Governance {},
+    }
+
+    impl ExecuteMsg {
+        /**
+         * Wrap the message into the `CosmosMsg` a cw3 or DAO DAO proposal
+         * executes against the contract at `contract_addr`
+         */
+        pub fn into_cosmos_msg(self, contract_addr: impl Into<String>) -> StdResult<CosmosMsg> {
+            Ok(WasmMsg::Execute {
+                contract_addr: contract_addr.into(),
+                msg: to_binary(&self)?,
+                funds: vec![],
+            }
+            .into())
+        }
     }
 }
 
@@ -185,6 +672,8 @@ pub type ConfigResponse = Config;
 
 pub type RoundResponse = FinishedRound;
 
+pub type PauseStateResponse = PauseState;
+
 #[cw_serde]
 pub struct StatusResponse {
     pub bidding_round: Option<NextRound>,
@@ -192,6 +681,62 @@ pub struct StatusResponse {
     pub current_time: Timestamp,
 }
 
+#[cw_serde]
+pub struct RoundSummary {
+    pub round: FinishedRound,
+    /* Both pools together */
+    pub total_amount: Uint128,
+    /* Close price over open price */
+    pub price_ratio: Decimal,
+    /* What a winning bet was paid per token staked, before the gaming fee. 1 when bets were refunded. */
+    pub payout_multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct RoundsResponse {
+    pub rounds: Vec<RoundSummary>,
+    /* Outcomes of the rounds returned */
+    pub bull_wins: u32,
+    pub bear_wins: u32,
+    pub draws: u32,
+    pub voided: u32,
+}
+
+#[cw_serde]
+pub struct OddsResponse {
+    pub round_id: Uint128,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    /* What a winning bet is paid per token staked; missing while nobody bet on that side */
+    pub bull_multiplier: Option<Decimal>,
+    pub bear_multiplier: Option<Decimal>,
+}
+
+#[cw_serde]
+pub struct SimulateBetResponse {
+    pub round_id: Uint128,
+    pub direction: Direction,
+    pub amount: Uint128,
+    /* Paid out if the bet wins and the pools don't change, net of the gaming fee */
+    pub payout: Uint128,
+    pub gaming_fee: Uint128,
+    pub odds_before: OddsResponse,
+    pub odds_after: OddsResponse,
+}
+
+#[cw_serde]
+pub struct EmergencyState {
+    pub triggered_at: Timestamp,
+    pub triggered_by: Addr,
+    /* Rounds whose bets can be withdrawn */
+    pub voided_rounds: Vec<Uint128>,
+}
+
+#[cw_serde]
+pub struct EmergencyResponse {
+    pub emergency: Option<EmergencyState>,
+}
+
 #[cw_serde]
 pub struct MyCurrentPositionResponse {
     pub live_bear_amount: Uint128,
@@ -231,6 +776,71 @@ pub struct AdminsResponse {
     pub admins: Vec<Addr>,
 }
 
+#[cw_serde]
+pub struct ClaimExpiryResponse {
+    pub round_id: Uint128,
+    /* None if the round hasn't finished or claims never expire */
+    pub expires_at: Option<Timestamp>,
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct PendingConfigChange {
+    pub id: u64,
+    pub config: ConfigUpdate,
+    pub proposer: Addr,
+    pub executable_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct PendingConfigChangesResponse {
+    pub changes: Vec<PendingConfigChange>,
+}
+
+#[cw_serde]
+pub enum OwnershipChange {
+    /* The candidate becomes an owner */
+    AddAdmin,
+    /* The candidate becomes an owner and the proposer no longer is */
+    Transfer,
+    /* The candidate contract becomes the only owner */
+    Governance,
+}
+
+impl std::fmt::Display for OwnershipChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OwnershipChange::AddAdmin => write!(f, "add_admin"),
+            OwnershipChange::Transfer => write!(f, "transfer"),
+            OwnershipChange::Governance => write!(f, "governance"),
+        }
+    }
+}
+
+#[cw_serde]
+pub struct OwnershipProposal {
+    pub candidate: Addr,
+    pub proposer: Addr,
+    pub change: OwnershipChange,
+    pub expires_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct OwnershipProposalResponse {
+    pub proposals: Vec<OwnershipProposal>,
+}
+
+#[cw_serde]
+pub struct GovernanceResponse {
+    pub governance: Option<Addr>,
+}
+
 #[cw_serde]
 pub struct WalletInfo {
     pub address: Addr,
@@ -242,11 +852,171 @@ pub struct TotalSpentResponse {
     pub total_spent: Uint128,
 }
 
+/**
+ * Kept up to date as the player bets and rounds close. A bet's outcome is
+ * counted once its round has been scored, whether or not it is ever claimed.
+ */
+#[cw_serde]
+#[derive(Default)]
+pub struct PlayerStats {
+    pub rounds_played: u64,
+    pub rounds_won: u64,
+    pub rounds_lost: u64,
+    /* One sided rounds, draws and voided rounds */
+    pub rounds_refunded: u64,
+    pub total_wagered: Uint128,
+    /* Staked on the bets settled so far */
+    pub settled_wagered: Uint128,
+    /* Paid back to the player net of the gaming fee, refunds included */
+    pub total_won: Uint128,
+    pub fees_paid: Uint128,
+    pub current_win_streak: u64,
+    pub best_win_streak: u64,
+}
+
+#[cw_serde]
+pub enum LeaderboardMetric {
+    NetPnl,
+    Volume,
+    /* Best win streak within the period */
+    WinStreak,
+}
+
+#[cw_serde]
+pub enum LeaderboardPeriod {
+    /* UTC days */
+    Daily,
+    /* Weeks starting on Monday 00:00 UTC */
+    Weekly,
+    AllTime,
+    /* The season's ranking, `at` is ignored */
+    Season { season_id: u64 },
+}
+
+/**
+ * A player's results within a leaderboard window, e.g. "weekly_2850"
+ */
+#[cw_serde]
+pub struct LeaderboardScore {
+    pub player: Addr,
+    pub window: String,
+    pub volume: Uint128,
+    pub settled_wagered: Uint128,
+    pub won: Uint128,
+    /* Bets settled, won, lost or refunded */
+    pub rounds: u64,
+    pub current_win_streak: u64,
+    pub best_win_streak: u64,
+}
+
+#[cw_serde]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub player: Addr,
+    /* Signed for the net PnL */
+    pub value: String,
+}
+
+#[cw_serde]
+pub struct LeaderboardResponse {
+    pub metric: LeaderboardMetric,
+    pub period: LeaderboardPeriod,
+    pub window: String,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[cw_serde]
+pub struct PlayerStatsResponse {
+    pub player: Addr,
+    pub stats: PlayerStats,
+    /* Signed, what the settled bets made or lost, e.g. "-1500" */
+    pub net_pnl: String,
+    /* Rounds won over rounds won or lost */
+    pub win_rate: Option<Decimal>,
+}
+
+/**
+ * Protocol wide numbers, kept in total and per UTC day. Bets count when they
+ * are placed, rounds when they close and payouts when they are claimed.
+ */
+#[cw_serde]
+#[derive(Default)]
+pub struct GlobalStats {
+    pub total_rounds: u64,
+    pub bull_wins: u64,
+    pub bear_wins: u64,
+    /* Rounds whose price didn't move */
+    pub draws: u64,
+    /* Rounds that couldn't be priced */
+    pub voided: u64,
+    pub volume: Vec<Coin>,
+    /* Gaming fees taken on claims */
+    pub fees: Vec<Coin>,
+    /* Sent to players net of the gaming fee, refunds included */
+    pub paid_out: Vec<Coin>,
+    /* Players betting for the first time, so the buckets add up to the total */
+    pub unique_players: u64,
+}
+
+#[cw_serde]
+pub struct DailyStats {
+    /* Days since the epoch */
+    pub day: u64,
+    pub stats: GlobalStats,
+}
+
+#[cw_serde]
+pub struct GlobalStatsResponse {
+    pub totals: GlobalStats,
+    /* Oldest first, days without activity are left out */
+    pub days: Vec<DailyStats>,
+}
+
+/**
+ * A tournament over the rounds closing between `start_time` and `end_time`.
+ * Players are ranked on the net PnL of their bets in these rounds before the
+ * gaming fee, and the top of the ranking shares the prize pool once the
+ * season is finalized. A bet counts once its round has closed and been
+ * scored, whether or not it is ever claimed.
+ */
+#[cw_serde]
+pub struct Season {
+    pub id: u64,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub min_bet: Uint128,
+    pub min_rounds: u64,
+    pub fee_share: Decimal,
+    /* Shares of the ranks nobody qualified for go to the winners pro rata */
+    pub prize_curve: Vec<Decimal>,
+    /* The token denom when the season was created, prizes are paid in it */
+    pub denom: String,
+    pub prize_pool: Uint128,
+    pub finalized: bool,
+    pub winners: Vec<SeasonPrize>,
+}
+
+#[cw_serde]
+pub struct SeasonPrize {
+    pub rank: u32,
+    pub player: Addr,
+    pub prize: Uint128,
+}
+
+#[cw_serde]
+pub struct SeasonsResponse {
+    pub seasons: Vec<Season>,
+}
+
 #[cw_serde]
 pub struct ClaimInfo {
     pub player: Addr,
     pub round_id: Uint128,
     pub claimed_amount: Uint128,
+    /* Who sent the claim; backfilled with the player by the 1.1.0 migration */
+    pub claimed_by: Option<Addr>,
+    /* Who the winnings were paid to */
+    pub recipient: Option<Addr>,
 }
 
 /// Primary key for claiminfo: (round_id, player)
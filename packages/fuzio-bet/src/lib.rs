@@ -1,2 +1,5 @@
 //members
+pub mod amm;
+pub mod events;
 pub mod fuzio_prediction_game;
+pub mod pyth;
@@ -0,0 +1,47 @@
+use cosmwasm_std::{Binary, Uint64};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/*
+ * The part of the Pyth price feed contract's interface the game uses. The
+ * responses aren't `cw_serde` so fields Pyth adds later don't break parsing.
+ */
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /* Signed price updates from Pyth's price service, paid for with funds */
+    UpdatePriceFeeds { data: Vec<Binary> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    PriceFeed { id: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeedResponse {
+    pub price_feed: PriceFeed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceFeed {
+    pub id: String,
+    pub price: Price,
+    pub ema_price: Price,
+}
+
+/**
+ * A price is `price * 10^expo`, give or take `conf * 10^expo`. Pyth's prices
+ * are signed, a negative one doesn't parse, which is fine as it can't price a
+ * bet token anyway.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Price {
+    pub price: Uint64,
+    pub conf: Uint64,
+    pub expo: i32,
+    /* Unix time in seconds */
+    pub publish_time: i64,
+}